@@ -201,7 +201,7 @@ pub fn About() -> impl IntoView {
     // you should ordinarily use a link instead,
     // both semantically and so your link will work before WASM loads
     view! {
-        <button on:click=move |_| navigate("/", Default::default())>"Home"</button>
+        <button on:click=move |_| navigate("/", Default::default()).forget()>"Home"</button>
         <h1>"About"</h1>
         <p>
             "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum."
@@ -34,7 +34,7 @@ use leptos_integration_utils::{
 use leptos_meta::ServerMetaContext;
 use leptos_router::{
     components::provide_server_redirect,
-    location::RequestUrl,
+    location::{RequestOrigin, RequestUrl},
     static_routes::{RegenerationFn, ResolvedStaticPath},
     ExpandOptionals, Method, PathSegment, RouteList, RouteListing, SsrMode,
 };
@@ -776,6 +776,14 @@ fn provide_contexts(
     let path = leptos_corrected_path(&req);
 
     provide_context(RequestUrl::new(&path));
+    {
+        let info = req.0.connection_info();
+        if let Some(origin) =
+            RequestOrigin::from_host(info.scheme(), info.host())
+        {
+            provide_context(origin);
+        }
+    }
     provide_context(meta_context.clone());
     provide_context(res_options.clone());
     provide_context(req);
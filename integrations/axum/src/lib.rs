@@ -65,9 +65,10 @@ use leptos_meta::ServerMetaContext;
 #[cfg(feature = "default")]
 use leptos_router::static_routes::ResolvedStaticPath;
 use leptos_router::{
-    components::provide_server_redirect, location::RequestUrl,
-    static_routes::RegenerationFn, ExpandOptionals, PathSegment, RouteList,
-    RouteListing, SsrMode,
+    components::provide_server_redirect,
+    location::{RequestOrigin, RequestUrl},
+    static_routes::RegenerationFn,
+    ExpandOptionals, PathSegment, RouteList, RouteListing, SsrMode,
 };
 use parking_lot::RwLock;
 use server_fn::{error::ServerFnErrorErr, redirect::REDIRECT_HEADER};
@@ -950,6 +951,23 @@ fn provide_contexts(
     default_res_options: ResponseOptions,
 ) {
     provide_context(RequestUrl::new(path));
+    if let Some(host) = parts
+        .headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+    {
+        // only trust X-Forwarded-Proto for the schemes a page can actually be served over
+        let scheme = parts
+            .headers
+            .get("x-forwarded-proto")
+            .and_then(|proto| proto.to_str().ok())
+            .filter(|proto| matches!(*proto, "http" | "https"))
+            .or_else(|| parts.uri.scheme_str())
+            .unwrap_or("http");
+        if let Some(origin) = RequestOrigin::from_host(scheme, host) {
+            provide_context(origin);
+        }
+    }
     provide_context(meta_context.clone());
     provide_context(parts);
     provide_context(default_res_options);
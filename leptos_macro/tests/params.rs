@@ -26,3 +26,15 @@ fn params_test() {
         user_info
     );
 }
+
+#[test]
+fn params_invalid_field_test() {
+    let mut map = leptos_router::params::ParamsMap::new();
+    map.insert("type", "twelve".to_owned());
+    let err = UserInfo::from_map(&map).unwrap_err();
+    assert!(matches!(
+        err,
+        leptos_router::params::ParamsError::InvalidParam { ref name, ref value, .. }
+            if name == "type" && value == "twelve"
+    ));
+}
@@ -1,34 +1,54 @@
 pub use super::{form::*, link::*};
 #[cfg(feature = "ssr")]
-use crate::location::RequestUrl;
+use crate::location::{RequestOrigin, RequestUrl};
 pub use crate::nested_router::Outlet;
 use crate::{
     flat_router::FlatRoutesView,
-    hooks::{use_matched, use_navigate},
+    generate_route_list::{generate_path, sitemap_entries, SitemapEntry},
+    hooks::{use_matched, use_navigate, BreadcrumbItem, RouteTitles},
     location::{
-        BrowserUrl, Location, LocationChange, LocationProvider, State, Url,
+        BrowserUrl, Location, LocationProvider, RouteError, RouterError, State,
+        Url,
+    },
+    navigate::{
+        Debouncer, NavigateOptions, NavigationDecision, NavigationError,
+        NavigationGuards, NavigationOutcome, NavigationTarget,
     },
-    navigate::NavigateOptions,
     nested_router::NestedRoutesView,
+    params::ParamsMap,
     resolve_path::resolve_path,
-    ChooseView, MatchNestedRoutes, NestedRoute, PossibleRouteMatch, RouteDefs,
+    sticky_query::StickyQuery,
+    ChooseView, MatchInterface, MatchNestedRoutes, NestedRoute, OnRouteEnter,
+    OnRouteLeave, PathSegment, PossibleRouteMatch, RouteDefs, RouterConfig,
     SsrMode,
 };
-use either_of::EitherOf3;
+use any_spawner::Executor;
+use either_of::{Either, EitherOf3};
+use futures::channel::oneshot;
 use leptos::{children, prelude::*};
+#[cfg(feature = "meta")]
+use leptos_meta::Link;
 use reactive_graph::{
     owner::{provide_context, use_context, Owner},
     signal::ArcRwSignal,
-    traits::{GetUntracked, ReadUntracked, Set},
+    traits::{GetUntracked, ReadUntracked, Set, With, WithUntracked},
     wrappers::write::SignalSetter,
 };
 use std::{
+    any::Any,
     borrow::Cow,
     fmt::{Debug, Display},
+    future::Future,
+    hash::{DefaultHasher, Hash, Hasher},
     mem,
-    sync::Arc,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     time::Duration,
 };
+use wasm_bindgen::JsValue;
 
 /// A wrapper that allows passing route definitions as children to a component like [`Routes`],
 /// [`FlatRoutes`], [`ParentRoute`], or [`ProtectedParentRoute`].
@@ -51,11 +71,59 @@ where
     }
 }
 
+/// A reactive key for a [`Router`].
+///
+/// Whenever the value returned by the key function changes, all of the router's routes are torn
+/// down and rebuilt from scratch, and any state the router has accumulated (like its history stack)
+/// is cleared.
+#[derive(Debug, Clone, Copy)]
+pub struct RouterKey(Memo<u64>);
+
+impl<F, K> From<F> for RouterKey
+where
+    F: Fn() -> K + Send + Sync + 'static,
+    K: Hash,
+{
+    fn from(key: F) -> Self {
+        Self(Memo::new(move |_| {
+            let mut hasher = DefaultHasher::new();
+            key().hash(&mut hasher);
+            hasher.finish()
+        }))
+    }
+}
+
+impl Track for RouterKey {
+    fn track(&self) {
+        self.0.track();
+    }
+}
+
 #[component(transparent)]
 pub fn Router<Chil>(
-    /// The base URL for the router. Defaults to `""`.
+    /// The base URL for the router. In the browser, this defaults to the path of the page's
+    /// `<base href>` element, if any, and otherwise to `""`.
+    ///
+    /// The `<base>` element is not read while rendering on the server, so apps that rely on it
+    /// should also set this prop explicitly when using server rendering.
     #[prop(optional, into)]
     base: Option<Cow<'static, str>>,
+    /// A reactive key: whenever it changes, the entire route tree is remounted and all router
+    /// state is reset. This is useful, for example, when switching between users in a
+    /// multi-tenant app.
+    #[prop(optional, into)]
+    key: Option<RouterKey>,
+    /// A last-resort fallback that replaces the router's routes if any of them throws an error
+    /// that is not caught by a nested `<ErrorBoundary/>`. It receives the error and the URL that
+    /// was being rendered. To try rendering the routes again (for example, from a "Retry"
+    /// button), call the function returned by
+    /// [`use_router_reload`](crate::hooks::use_router_reload).
+    #[prop(optional, into)]
+    error_boundary: Option<Callback<RouterError, AnyView>>,
+    /// Options for the router, such as how it scrolls and how long its history stack can grow.
+    /// The `base` prop, if set, takes priority over [`RouterConfig::base`].
+    #[prop(optional)]
+    config: RouterConfig,
     /// A signal that will be set while the navigation process is underway.
     #[prop(optional, into)]
     set_is_routing: Option<SignalSetter<bool>>,
@@ -74,17 +142,25 @@ where
     #[cfg(feature = "ssr")]
     let (location_provider, current_url, redirect_hook) = {
         let req = use_context::<RequestUrl>().expect("no RequestUrl provided");
-        let parsed = req.parse().expect("could not parse RequestUrl");
+        let parsed = use_context::<RequestOrigin>()
+            .and_then(|origin| req.parse_with_base(origin.as_ref()).ok())
+            .map_or_else(|| req.parse(), Ok)
+            .expect("could not parse RequestUrl");
         let current_url = ArcRwSignal::new(parsed);
 
-        (None, current_url, Box::new(move |_: &str| {}))
+        (None::<BrowserUrl>, current_url, Box::new(move |_: &str| {}))
     };
 
+    let base = base.or_else(|| config.get_base().cloned());
+    #[cfg(not(feature = "ssr"))]
+    let base = base.or_else(BrowserUrl::base_from_document);
+    provide_context(config.clone());
+
     #[cfg(not(feature = "ssr"))]
     let (location_provider, current_url, redirect_hook) = {
         let owner = Owner::current();
         let location =
-            BrowserUrl::new().expect("could not access browser navigation"); // TODO options here
+            BrowserUrl::new().expect("could not access browser navigation");
         location.init(base.clone());
         provide_context(location.clone());
         let current_url = location.as_url().clone();
@@ -98,29 +174,114 @@ where
         (Some(location), current_url, redirect_hook)
     };
     // provide router context
-    let state = ArcRwSignal::new(State::new(None));
-    let location = Location::new(current_url.read_only(), state.read_only());
+    let state = location_provider
+        .as_ref()
+        .map(|provider| provider.state.clone())
+        .unwrap_or_else(|| ArcRwSignal::new(State::new(None)));
+    let location = match location_provider.as_ref() {
+        Some(provider) => {
+            let hash = provider.hash.read_only();
+            Location::new_with_hash(
+                current_url.read_only(),
+                state.read_only(),
+                Memo::new(move |_| hash.get()),
+            )
+        }
+        None => Location::new(current_url.read_only(), state.read_only()),
+    };
 
     // set server function redirect hook
     _ = server_fn::redirect::set_redirect_hook(redirect_hook);
 
-    provide_context(RouterContext {
-        base,
+    let guards = location_provider
+        .as_ref()
+        .map(|provider| provider.guards.clone())
+        .unwrap_or_default();
+    let sticky_query = location_provider
+        .as_ref()
+        .map(|provider| provider.sticky_query.clone())
+        .unwrap_or_default();
+    let router_error = location_provider
+        .as_ref()
+        .map(|provider| provider.history_error.clone())
+        .unwrap_or_default();
+    // share one debounce window between `use_navigate` and link clicks
+    let debouncer = location_provider
+        .as_ref()
+        .map(|provider| provider.debouncer.clone())
+        .unwrap_or_default();
+    let router = RouterContext {
+        base: ArcRwSignal::new(base),
         current_url,
         location,
         state,
         set_is_routing,
         query_mutations: Default::default(),
+        provider: location_provider
+            .clone()
+            .map(|provider| Arc::new(provider) as Arc<dyn Any + Send + Sync>),
         location_provider,
+        key,
+        routes: Default::default(),
+        error_boundary,
+        reload: ArcTrigger::new(),
+        guards,
+        navigation_id: Default::default(),
+        route_titles: Default::default(),
+        breadcrumbs: Default::default(),
+        route_error: Default::default(),
+        router_error,
+        config,
+        debouncer,
+        sticky_query,
+    };
+    router.guards.set_is_routing(set_is_routing);
+    provide_context(router.clone());
+
+    // keep the document title in sync with the titles set by `use_route_title`
+    Effect::new({
+        let route_titles = router.route_titles.clone();
+        move |_| {
+            if let Some(title) = route_titles.current() {
+                document().set_title(&title);
+            }
+        }
     });
 
+    // keep the `<link rel="canonical">` tag in sync with the current URL
+    #[cfg(feature = "meta")]
+    let canonical_link =
+        router.config.get_canonical_base().is_some().then(|| {
+            let config = router.config.clone();
+            let location = router.location.clone();
+            move || {
+                let href = location.pathname.with(|path| {
+                    location
+                        .search
+                        .with(|search| config.canonical_url(path, search))
+                });
+                view! { <Link rel="canonical" href=href.unwrap_or_default()/> }
+            }
+        });
+
+    if let Some(key) = key {
+        Effect::watch(
+            move || key.track(),
+            move |_, _, _| router.reset(),
+            false,
+        );
+    }
+
+    #[cfg(not(feature = "meta"))]
+    let canonical_link = ();
+
     let children = children.into_inner();
-    children()
+    (canonical_link, children())
 }
 
 #[derive(Clone)]
 pub(crate) struct RouterContext {
-    pub base: Option<Cow<'static, str>>,
+    pub base: ArcRwSignal<Option<Cow<'static, str>>>,
     pub current_url: ArcRwSignal<Url>,
     pub location: Location,
     pub state: ArcRwSignal<State>,
@@ -128,29 +289,163 @@ pub(crate) struct RouterContext {
     pub query_mutations:
         ArcStoredValue<Vec<(Oco<'static, str>, Option<String>)>>,
     pub location_provider: Option<BrowserUrl>,
+    pub provider: Option<Arc<dyn Any + Send + Sync>>,
+    pub key: Option<RouterKey>,
+    pub routes: ArcStoredValue<Vec<RegisteredRoute>>,
+    pub error_boundary: Option<Callback<RouterError, AnyView>>,
+    pub reload: ArcTrigger,
+    pub guards: NavigationGuards,
+    pub navigation_id: Arc<AtomicUsize>,
+    pub route_titles: RouteTitles,
+    pub breadcrumbs: ArcRwSignal<Vec<BreadcrumbItem>>,
+    pub route_error: ArcRwSignal<Option<RouteError>>,
+    pub router_error: ArcRwSignal<Option<RouterError>>,
+    pub config: RouterConfig,
+    pub debouncer: Debouncer,
+    pub sticky_query: StickyQuery,
 }
 
 impl RouterContext {
-    pub fn navigate(&self, path: &str, options: NavigateOptions) {
+    /// Returns the active [`LocationProvider`], if it is of type `P`.
+    pub fn get_provider<P>(&self) -> Option<Arc<P>>
+    where
+        P: LocationProvider + Send + Sync,
+    {
+        self.provider.clone()?.downcast::<P>().ok()
+    }
+
+    /// Clears any state the router has accumulated over the course of navigations.
+    pub fn reset(&self) {
+        self.query_mutations.write_value().clear();
+        self.state.set(State::new(None));
+        if let Some(location_provider) = &self.location_provider {
+            location_provider.reset();
+        }
+    }
+
+    /// Tears down the current route tree and renders it again from scratch, for the current URL.
+    pub fn reload(&self) {
+        self.reload.notify();
+    }
+
+    /// Records the paths and names of a set of route definitions, so that they can be listed or
+    /// linked to later.
+    pub fn register_routes<Defs>(&self, routes: &RouteDefs<Defs>)
+    where
+        Defs: MatchNestedRoutes,
+    {
+        let (base, generated) = routes.generate_routes();
+        let base =
+            base.map(|base| PathSegment::Static(base.to_string().into()));
+        let mut registered = self.routes.write_value();
+        for data in generated {
+            let route = RegisteredRoute {
+                name: data.name,
+                path: base.iter().cloned().chain(data.segments).collect(),
+            };
+            if !registered.contains(&route) {
+                registered.push(route);
+            }
+        }
+    }
+
+    /// Lists every registered route that has no parameters or wildcards, as sitemap entries.
+    pub fn generate_sitemap(&self) -> Vec<SitemapEntry> {
+        sitemap_entries(
+            self.routes
+                .read_value()
+                .iter()
+                .map(|route| route.path.as_slice()),
+        )
+    }
+
+    /// Generates a path to the route with the given name, filling in its params from `params`.
+    /// Any params the route does not use are added as query params.
+    pub fn generate_path(
+        &self,
+        name: &str,
+        params: ParamsMap,
+    ) -> Result<String, RouterError> {
+        let routes = self.routes.read_value();
+        let route = routes
+            .iter()
+            .find(|route| route.name.as_deref() == Some(name))
+            .ok_or_else(|| RouterError::UnknownRoute(name.to_string()))?;
+        generate_path(&route.path, params).map_err(|param| {
+            RouterError::MissingParam {
+                route: name.to_string(),
+                param,
+            }
+        })
+    }
+
+    pub fn navigate(
+        &self,
+        path: &str,
+        options: NavigateOptions,
+    ) -> NavigationOutcome {
+        let debounce = options
+            .debounce
+            .or(self.config.get_location_options().navigation_debounce);
+        let Some(delay) = debounce else {
+            return self.navigate_inner(path, options, true);
+        };
+
+        // wait to see whether another navigation starts first
+        let waiting = self.debouncer.wait(delay);
+        let this = self.clone();
+        let path = path.to_string();
+        let (tx, rx) = oneshot::channel();
+        Executor::spawn_local(async move {
+            let result = if waiting.await.is_ok() {
+                this.navigate_inner(&path, options, true).await
+            } else {
+                Err(NavigationError::Cancelled)
+            };
+            _ = tx.send(result);
+        });
+        NavigationOutcome::pending(async move {
+            rx.await.unwrap_or(Err(NavigationError::Cancelled))
+        })
+    }
+
+    fn navigate_inner(
+        &self,
+        path: &str,
+        options: NavigateOptions,
+        check_guards: bool,
+    ) -> NavigationOutcome {
         let current = self.current_url.read_untracked();
         let resolved_to = if options.resolve {
-            resolve_path(
-                self.base.as_deref().unwrap_or_default(),
-                path,
-                // TODO this should be relative to the current *Route*, I think...
-                Some(current.path()),
-            )
+            self.base.with_untracked(|base| {
+                resolve_path(
+                    base.as_deref().unwrap_or_default(),
+                    path,
+                    // TODO this should be relative to the current *Route*, I think...
+                    Some(current.path()),
+                )
+                .into_owned()
+            })
         } else {
-            resolve_path("", path, None)
+            resolve_path("", path, None).into_owned()
         };
 
         let mut url = match BrowserUrl::parse(&resolved_to) {
             Ok(url) => url,
             Err(e) => {
                 leptos::logging::error!("Error parsing URL: {e:?}");
-                return;
+                return NavigationOutcome::ready(Err(
+                    NavigationError::ParseError(format!("{e:?}")),
+                ));
             }
         };
+        if self.config.is_strict() {
+            if let Err(e) = url.validate_encoding() {
+                return NavigationOutcome::ready(Err(
+                    NavigationError::ParseError(e.to_string()),
+                ));
+            }
+        }
         let query_mutations =
             mem::take(&mut *self.query_mutations.write_value());
         if !query_mutations.is_empty() {
@@ -168,11 +463,82 @@ impl RouterContext {
                 .into()
         }
 
+        self.sticky_query.apply(&current, &mut url);
+
         if url.origin() != current.origin() {
             window().location().set_href(path).unwrap();
-            return;
+            return NavigationOutcome::ready(Ok(()));
+        }
+
+        let target = NavigationTarget {
+            from: current.clone(),
+            to: url,
+        };
+        drop(current);
+
+        // interceptors run before any guards, and can redirect or cancel the navigation
+        if check_guards {
+            let decision: NavigationDecision = self
+                .guards
+                .intercept(&target.to.to_full_path(), &options)
+                .into();
+            if decision != NavigationDecision::Allow {
+                return self.complete_guarded(target.to, decision, options);
+            }
+        }
+
+        let id = self.navigation_id.fetch_add(1, Ordering::Relaxed) + 1;
+
+        // async guards have to be awaited before the navigation can continue
+        if check_guards && self.guards.has_async() {
+            let this = self.clone();
+            let decision = self.guards.check_async(target.clone());
+            let (tx, rx) = oneshot::channel();
+            Executor::spawn_local(async move {
+                let decision = decision.await;
+                // don't complete the navigation if another one has started in the meantime
+                let result = if this.navigation_id.load(Ordering::Relaxed) == id
+                {
+                    this.complete_guarded(target.to, decision, options).await
+                } else {
+                    Err(NavigationError::Cancelled)
+                };
+                _ = tx.send(result);
+            });
+            return NavigationOutcome::pending(async move {
+                rx.await.unwrap_or(Err(NavigationError::Cancelled))
+            });
+        }
+
+        let decision = if check_guards {
+            self.guards.check(&target)
+        } else {
+            NavigationDecision::Allow
+        };
+        self.complete_guarded(target.to, decision, options)
+    }
+
+    /// Finishes a navigation to `url` once the guards have decided what should happen to it.
+    fn complete_guarded(
+        &self,
+        url: Url,
+        decision: NavigationDecision,
+        options: NavigateOptions,
+    ) -> NavigationOutcome {
+        match decision {
+            NavigationDecision::Allow => {}
+            NavigationDecision::Deny => {
+                return NavigationOutcome::ready(Err(
+                    NavigationError::GuardRejected,
+                ))
+            }
+            NavigationDecision::Redirect(path) => {
+                return self.navigate_inner(&path, options, false);
+            }
         }
 
+        let current = self.current_url.read_untracked();
+
         // update state signal, if necessary
         if options.state != self.state.get_untracked() {
             self.state.set(options.state.clone());
@@ -180,31 +546,110 @@ impl RouterContext {
 
         // update URL signal, if necessary
         let value = url.to_full_path();
-        if current != url {
+        if current.to_full_path() != value {
             drop(current);
+            if !options.replace {
+                if let Some(provider) = &self.location_provider {
+                    provider.save_scroll_position();
+                }
+            }
             self.current_url.set(url);
         }
 
         if let Some(location_provider) = &self.location_provider {
-            location_provider.complete_navigation(&LocationChange {
-                value,
-                replace: options.replace,
-                scroll: options.scroll,
-                state: options.state,
-            });
+            location_provider
+                .complete_navigation(&options.into_location_change(value));
+        }
+        NavigationOutcome::ready(Ok(()))
+    }
+
+    /// Moves `delta` steps through the browser's session history, backward if `delta` is
+    /// negative and forward if it is positive. Does nothing if `delta` is `0`.
+    ///
+    /// This is asynchronous: the browser fires a `popstate` event once the traversal has
+    /// happened, and the router's reactive state (like the current URL) is only updated when
+    /// that event is handled, not when this method returns. This does nothing during server
+    /// rendering.
+    pub fn go(&self, delta: i32) {
+        if delta == 0 || self.location_provider.is_none() {
+            return;
+        }
+        if let Err(e) = window().history().and_then(|h| h.go_with_delta(delta))
+        {
+            leptos::logging::error!("Error traversing history: {e:?}");
+        }
+    }
+
+    /// Replaces the URL and [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state)
+    /// of the current history entry, without navigating.
+    ///
+    /// This updates [`Location::state`] but not the router's current URL, so routes are not
+    /// matched again. It is intended for storing data like a scroll position in the current
+    /// history entry; `url` should usually be the current URL. During server rendering, only the
+    /// state signal is updated.
+    pub fn replace_state(
+        &self,
+        url: &str,
+        state: impl Into<State>,
+    ) -> Result<(), JsValue> {
+        let state = state.into();
+        if self.location_provider.is_some() {
+            window().history()?.replace_state_with_url(
+                &state.to_js_value(),
+                "",
+                Some(url),
+            )?;
+        }
+        self.state.set(state);
+        Ok(())
+    }
+
+    /// A signal that is `true` while a client-side navigation is in progress. It is always
+    /// `false` during server rendering.
+    pub fn navigation_pending(&self) -> ReadSignal<bool> {
+        match &self.location_provider {
+            Some(provider) => provider.navigation_pending.read_only().into(),
+            None => ArcRwSignal::new(false).read_only().into(),
         }
     }
 
+    /// Moves one step backward through the browser's session history.
+    ///
+    /// Like [`RouterContext::go`], this is asynchronous.
+    pub fn back(&self) {
+        self.go(-1);
+    }
+
+    /// Moves one step forward through the browser's session history.
+    ///
+    /// Like [`RouterContext::go`], this is asynchronous.
+    pub fn forward(&self) {
+        self.go(1);
+    }
+
+    /// Resolves `path` relative to `from` and the router's base. This tracks the base, so it can
+    /// be used in a reactive context.
     pub fn resolve_path<'a>(
-        &'a self,
+        &self,
         path: &'a str,
         from: Option<&'a str>,
     ) -> Cow<'a, str> {
-        let base = self.base.as_deref().unwrap_or_default();
-        resolve_path(base, path, from)
+        self.base.with(|base| {
+            Cow::Owned(
+                resolve_path(base.as_deref().unwrap_or_default(), path, from)
+                    .into_owned(),
+            )
+        })
     }
 }
 
+/// A route that has been registered with a [`RouterContext`] by a [`Routes`] or [`FlatRoutes`].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RegisteredRoute {
+    pub name: Option<Cow<'static, str>>,
+    pub path: Vec<PathSegment>,
+}
+
 impl Debug for RouterContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RouterContext")
@@ -232,14 +677,21 @@ where
     Fallback: IntoView + 'static,
 {
     let location = use_context::<BrowserUrl>();
+    let router = use_context::<RouterContext>()
+        .expect("<Routes> should be used inside a <Router> component");
     let RouterContext {
         current_url,
         base,
         set_is_routing,
+        key,
+        error_boundary,
+        reload,
+        breadcrumbs,
+        route_error,
         ..
-    } = use_context()
-        .expect("<Routes> should be used inside a <Router> component");
-    let base = base.map(|base| {
+    } = router.clone();
+    // the route definitions are built once, so they use the base at the time they are mounted
+    let base = base.get_untracked().map(|base| {
         let mut base = Oco::from(base);
         base.upgrade_inplace();
         base
@@ -248,22 +700,125 @@ where
         children.into_inner(),
         base.clone().unwrap_or_default(),
     );
+    router.register_routes(&routes);
+    prefetch_routes(location.as_ref(), &routes);
     let outer_owner =
         Owner::current().expect("creating Routes, but no Owner was found");
-    move || {
-        current_url.track();
-        outer_owner.with(|| {
-            current_url.read_untracked().provide_server_action_error()
+    let url = current_url.clone();
+    let routes_view = move || {
+        // if the router is keyed or reloaded, the whole route tree is rebuilt
+        // under a fresh owner, rather than diffed against the previous matches
+        let outer_owner = keyed_owner(key, &reload, &outer_owner);
+        let location = location.clone();
+        let routes = routes.clone();
+        let current_url = current_url.clone();
+        let base = base.clone();
+        let fallback = fallback.clone();
+        let breadcrumbs = breadcrumbs.clone();
+        let route_error = route_error.clone();
+        move || {
+            current_url.track();
+            outer_owner.with(|| {
+                current_url.read_untracked().provide_server_action_error()
+            });
+            NestedRoutesView {
+                location: location.clone(),
+                routes: routes.clone(),
+                outer_owner: outer_owner.clone(),
+                current_url: current_url.clone(),
+                base: base.clone(),
+                fallback: fallback.clone(),
+                set_is_routing,
+                transition,
+                breadcrumbs: breadcrumbs.clone(),
+                route_error: route_error.clone(),
+            }
+        }
+    };
+    with_error_boundary(error_boundary, url, router.route_error, routes_view)
+}
+
+/// Preloads the views for any route the location provider asks to prefetch.
+fn prefetch_routes<Defs>(
+    location: Option<&BrowserUrl>,
+    routes: &RouteDefs<Defs>,
+) where
+    Defs: MatchNestedRoutes + Clone + 'static,
+{
+    fn preload<M>(matched: M) -> Pin<Box<dyn Future<Output = ()>>>
+    where
+        M: MatchInterface,
+    {
+        let (view, child) = matched.into_view_and_child();
+        Box::pin(async move {
+            view.preload().await;
+            if let Some(child) = child {
+                preload(child).await;
+            }
+        })
+    }
+
+    if let Some(location) = location {
+        let prefetch = location.prefetch.clone();
+        let routes = routes.clone();
+        Effect::new(move |_| {
+            let matched = prefetch.with(|url| {
+                url.as_ref().and_then(|url| routes.match_route(url.path()))
+            });
+            if let Some(matched) = matched {
+                Executor::spawn_local(preload(matched));
+            }
         });
-        NestedRoutesView {
-            location: location.clone(),
-            routes: routes.clone(),
-            outer_owner: outer_owner.clone(),
-            current_url: current_url.clone(),
-            base: base.clone(),
-            fallback: fallback.clone(),
-            set_is_routing,
-            transition,
+    }
+}
+
+/// Returns the owner under which routes should be built. The caller reruns whenever the
+/// router's key changes or the router is reloaded, and each run gets a fresh owner, so that the
+/// route tree is rebuilt from scratch rather than diffed against the previous matches.
+fn keyed_owner(
+    key: Option<RouterKey>,
+    reload: &ArcTrigger,
+    outer_owner: &Owner,
+) -> Owner {
+    if let Some(key) = key {
+        key.track();
+    }
+    reload.track();
+    Owner::current().unwrap_or_else(|| outer_owner.clone())
+}
+
+/// Wraps the routes in an `<ErrorBoundary/>` that shows the router's global error fallback, if
+/// one has been set.
+fn with_error_boundary(
+    error_boundary: Option<Callback<RouterError, AnyView>>,
+    current_url: ArcRwSignal<Url>,
+    route_error: ArcRwSignal<Option<RouteError>>,
+    routes: impl IntoView + 'static,
+) -> impl IntoView {
+    match error_boundary {
+        None => Either::Left(routes),
+        Some(error_boundary) => {
+            let fallback = move |errors: ArcRwSignal<Errors>| {
+                // make the first error available to `use_route_error`
+                let first = errors.with(|errors| {
+                    errors.iter().next().map(|(_, error)| error.clone())
+                });
+                route_error.set(first.map(RouteError::from));
+                let message = errors.with(|errors| {
+                    errors
+                        .iter()
+                        .map(|(_, error)| error.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                });
+                error_boundary.run(RouterError::Route {
+                    url: current_url.read_untracked().to_full_path(),
+                    message,
+                })
+            };
+            Either::Right(view! {
+                <ErrorBoundary fallback>{routes}</ErrorBoundary>
+            })
         }
     }
 }
@@ -285,17 +840,22 @@ where
     Fallback: IntoView + 'static,
 {
     let location = use_context::<BrowserUrl>();
+    let router = use_context::<RouterContext>()
+        .expect("<FlatRoutes> should be used inside a <Router> component");
     let RouterContext {
         current_url,
         base,
         set_is_routing,
+        key,
+        error_boundary,
+        reload,
+        route_error,
         ..
-    } = use_context()
-        .expect("<FlatRoutes> should be used inside a <Router> component");
+    } = router.clone();
 
     // TODO base
     #[allow(unused)]
-    let base = base.map(|base| {
+    let base = base.get_untracked().map(|base| {
         let mut base = Oco::from(base);
         base.upgrade_inplace();
         base
@@ -304,25 +864,38 @@ where
         children.into_inner(),
         base.clone().unwrap_or_default(),
     );
+    router.register_routes(&routes);
+    prefetch_routes(location.as_ref(), &routes);
 
     let outer_owner =
         Owner::current().expect("creating Router, but no Owner was found");
 
-    move || {
-        current_url.track();
-        outer_owner.with(|| {
-            current_url.read_untracked().provide_server_action_error()
-        });
-        FlatRoutesView {
-            current_url: current_url.clone(),
-            location: location.clone(),
-            routes: routes.clone(),
-            fallback: fallback.clone(),
-            outer_owner: outer_owner.clone(),
-            set_is_routing,
-            transition,
+    let url = current_url.clone();
+    let routes_view = move || {
+        let outer_owner = keyed_owner(key, &reload, &outer_owner);
+        let location = location.clone();
+        let routes = routes.clone();
+        let current_url = current_url.clone();
+        let fallback = fallback.clone();
+        let route_error = route_error.clone();
+        move || {
+            current_url.track();
+            outer_owner.with(|| {
+                current_url.read_untracked().provide_server_action_error()
+            });
+            FlatRoutesView {
+                current_url: current_url.clone(),
+                location: location.clone(),
+                routes: routes.clone(),
+                fallback: fallback.clone(),
+                outer_owner: outer_owner.clone(),
+                set_is_routing,
+                transition,
+                route_error: route_error.clone(),
+            }
         }
-    }
+    };
+    with_error_boundary(error_boundary, url, router.route_error, routes_view)
 }
 
 /// Describes a portion of the nested layout of the app, specifying the route it should match
@@ -340,6 +913,27 @@ pub fn Route<Segments, View>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// A name for this route, which can be used to generate a path to it with
+    /// [`use_generate_path`](crate::hooks::use_generate_path).
+    #[prop(optional, into)]
+    name: Option<Cow<'static, str>>,
+    /// The name of the parent route's [`Outlet`] that this route is displayed in. Routes for a
+    /// named outlet are matched alongside the parent's other child routes, rather than instead of
+    /// them. Defaults to the parent's unnamed outlet.
+    #[prop(optional)]
+    outlet: Option<&'static str>,
+    /// Called when the router enters this route, with the params it matched, once the route's
+    /// data has loaded. The navigation does not complete until the returned future resolves.
+    #[prop(optional, into)]
+    on_enter: Option<OnRouteEnter>,
+    /// Called when the router leaves this route, before the route that replaces it is loaded.
+    /// The navigation does not complete until the returned future resolves.
+    #[prop(optional, into)]
+    on_leave: Option<OnRouteLeave>,
+    /// A title for this route, used as its label in the trail returned by
+    /// [`use_breadcrumbs`](crate::hooks::use_breadcrumbs).
+    #[prop(optional, into)]
+    title: Option<Cow<'static, str>>,
 ) -> <NestedRoute<Segments, (), (), View> as IntoMaybeErased>::Output
 where
     View: ChooseView + Clone + 'static,
@@ -347,6 +941,11 @@ where
 {
     NestedRoute::new(path, view)
         .ssr_mode(ssr)
+        .name(name)
+        .outlet(outlet)
+        .on_enter(on_enter)
+        .on_leave(on_leave)
+        .title(title)
         .into_maybe_erased()
 }
 
@@ -367,6 +966,28 @@ pub fn ParentRoute<Segments, View, Children>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// A name for this route, which can be used to generate a path to it with
+    /// [`use_generate_path`](crate::hooks::use_generate_path). A child route with an empty path
+    /// shares this name, unless it has its own.
+    #[prop(optional, into)]
+    name: Option<Cow<'static, str>>,
+    /// The name of the parent route's [`Outlet`] that this route is displayed in. Routes for a
+    /// named outlet are matched alongside the parent's other child routes, rather than instead of
+    /// them. Defaults to the parent's unnamed outlet.
+    #[prop(optional)]
+    outlet: Option<&'static str>,
+    /// Called when the router enters this route, with the params it matched, once the route's
+    /// data has loaded. The navigation does not complete until the returned future resolves.
+    #[prop(optional, into)]
+    on_enter: Option<OnRouteEnter>,
+    /// Called when the router leaves this route, before the route that replaces it is loaded.
+    /// The navigation does not complete until the returned future resolves.
+    #[prop(optional, into)]
+    on_leave: Option<OnRouteLeave>,
+    /// A title for this route, used as its label in the trail returned by
+    /// [`use_breadcrumbs`](crate::hooks::use_breadcrumbs).
+    #[prop(optional, into)]
+    title: Option<Cow<'static, str>>,
 ) -> <NestedRoute<Segments, Children, (), View> as IntoMaybeErased>::Output
 where
     View: ChooseView + Clone + 'static,
@@ -376,6 +997,11 @@ where
     let children = children.into_inner();
     NestedRoute::new(path, view)
         .ssr_mode(ssr)
+        .name(name)
+        .outlet(outlet)
+        .on_enter(on_enter)
+        .on_leave(on_leave)
+        .title(title)
         .child(children)
         .into_maybe_erased()
 }
@@ -576,18 +1202,9 @@ pub fn Redirect<P>(
 
     // redirect on the server
     if let Some(redirect_fn) = use_context::<ServerRedirectFunction>() {
-        match resolve_path("", &path, Some(&use_matched().get_untracked())) {
-            Some(path) => (redirect_fn.f)(&path),
-            None => {
-                if cfg!(feature = "ssr") {
-                    #[cfg(feature = "tracing")]
-                    tracing::warn!("Error resolving relative URL.");
-
-                    #[cfg(not(feature = "tracing"))]
-                    eprintln!("Error resolving relative URL.");
-                }
-            }
-        }
+        let matched = use_matched().get_untracked();
+        let path = resolve_path("", &path, Some(&matched));
+        (redirect_fn.f)(&path);
     }
     // redirect on the client
     else {
@@ -0,0 +1,247 @@
+use crate::location::{
+    BrowserUrlOptions, ScrollBehavior, ScrollConfig, ScrollRestoration,
+    TrailingSlash,
+};
+use std::{borrow::Cow, time::Duration};
+
+/// Options for a [`Router`](crate::components::Router), collected in one place.
+///
+/// The router provides its config via context, and each part of the router reads the options it
+/// needs from there. Create one with [`RouterConfig::new`] and the builder methods:
+///
+/// ```rust
+/// # use leptos_router::{RouterConfig, location::ScrollBehavior};
+/// let config = RouterConfig::new()
+///     .base("/app")
+///     .scroll_behavior(ScrollBehavior::Smooth)
+///     .history_limit(20);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouterConfig {
+    base: Option<Cow<'static, str>>,
+    location_options: BrowserUrlOptions,
+    scroll_config: ScrollConfig,
+    scroll_restoration: ScrollRestoration,
+    strict_mode: bool,
+    canonical_base: Option<String>,
+    canonical_include_search: bool,
+    trailing_slash: TrailingSlash,
+}
+
+impl RouterConfig {
+    /// Creates a config with all of the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the base URL for the router. See the `base` prop of
+    /// [`Router`](crate::components::Router).
+    pub fn base(mut self, base: impl Into<Cow<'static, str>>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Sets how the page is scrolled at the end of each navigation.
+    pub fn scroll_behavior(mut self, behavior: ScrollBehavior) -> Self {
+        self.scroll_config.behavior = behavior;
+        self
+    }
+
+    /// Sets all of the scroll options at once.
+    pub fn scroll_config(mut self, scroll_config: ScrollConfig) -> Self {
+        self.scroll_config = scroll_config;
+        self
+    }
+
+    /// Sets whether the scroll position of each history entry is restored when the user goes back
+    /// or forward to it. See [`ScrollRestoration`].
+    pub fn scroll_restoration(
+        mut self,
+        scroll_restoration: ScrollRestoration,
+    ) -> Self {
+        self.scroll_restoration = scroll_restoration;
+        self
+    }
+
+    /// Sets whether `rel="noopener noreferrer"` is added to `target="_blank"` links when they are
+    /// clicked. See [`BrowserUrlOptions::enforce_noopener`].
+    pub fn enforce_noopener(mut self, enforce_noopener: bool) -> Self {
+        self.location_options.enforce_noopener = enforce_noopener;
+        self
+    }
+
+    /// Sets the maximum number of entries kept in the in-app history stack. See
+    /// [`BrowserUrlOptions::history_limit`].
+    pub fn history_limit(mut self, history_limit: usize) -> Self {
+        self.location_options.history_limit = history_limit;
+        self
+    }
+
+    /// Sets whether a `leptos:navigate` event is dispatched on `window` after each navigation. See
+    /// [`BrowserUrlOptions::dispatch_navigation_event`].
+    pub fn dispatch_navigation_event(mut self, dispatch: bool) -> Self {
+        self.location_options.dispatch_navigation_event = dispatch;
+        self
+    }
+
+    /// Debounces every navigation by the given delay, so that only the last of several that
+    /// start in quick succession happens. See [`BrowserUrlOptions::navigation_debounce`].
+    pub fn navigation_debounce(mut self, delay: Duration) -> Self {
+        self.location_options.navigation_debounce = Some(delay);
+        self
+    }
+
+    /// Sets whether the end of each navigation is wrapped in a view transition. See
+    /// [`BrowserUrlOptions::view_transitions`].
+    pub fn use_view_transitions(mut self, view_transitions: bool) -> Self {
+        self.location_options.view_transitions = view_transitions;
+        self
+    }
+
+    /// Sets all of the browser location options at once.
+    pub fn location_options(mut self, options: BrowserUrlOptions) -> Self {
+        self.location_options = options;
+        self
+    }
+
+    /// If `true`, navigations to URLs that are not validly percent-encoded fail with a
+    /// [`NavigationError::ParseError`](crate::NavigationError::ParseError), rather than being
+    /// decoded leniently. In debug builds, starting a navigation while another one is still
+    /// pending also panics, rather than only logging a warning.
+    ///
+    /// Defaults to `false`.
+    pub fn strict_mode(mut self, strict_mode: bool) -> Self {
+        self.strict_mode = strict_mode;
+        self
+    }
+
+    /// Sets how a trailing slash is treated when URLs are normalized with
+    /// [`LocationProvider::canonicalize`](crate::location::LocationProvider::canonicalize).
+    ///
+    /// Defaults to [`TrailingSlash::Exact`], since `/foo` and `/foo/` can match different routes.
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Adds a `<link rel="canonical">` tag to the page, pointing to the current path on the given
+    /// origin (like `https://example.com`). The tag is updated after each navigation.
+    ///
+    /// This requires the `meta` feature, and a [`leptos_meta`] context provided with
+    /// `provide_meta_context()`.
+    pub fn canonical_base(mut self, canonical_base: impl Into<String>) -> Self {
+        self.canonical_base = Some(canonical_base.into());
+        self
+    }
+
+    /// Sets whether the URL in the `<link rel="canonical">` tag includes the current query
+    /// string. See [`canonical_base`](Self::canonical_base).
+    ///
+    /// Defaults to `false`.
+    pub fn canonical_include_search(mut self, include_search: bool) -> Self {
+        self.canonical_include_search = include_search;
+        self
+    }
+
+    /// The base URL for the router, if one has been set.
+    pub fn get_base(&self) -> Option<&Cow<'static, str>> {
+        self.base.as_ref()
+    }
+
+    /// The options for the browser location provider.
+    pub fn get_location_options(&self) -> &BrowserUrlOptions {
+        &self.location_options
+    }
+
+    /// The scroll options.
+    pub fn get_scroll_config(&self) -> ScrollConfig {
+        self.scroll_config
+    }
+
+    /// The scroll restoration options.
+    pub fn get_scroll_restoration(&self) -> ScrollRestoration {
+        self.scroll_restoration
+    }
+
+    /// How a trailing slash is treated when URLs are normalized.
+    pub fn get_trailing_slash(&self) -> TrailingSlash {
+        self.trailing_slash
+    }
+
+    /// The origin used for the `<link rel="canonical">` tag, if one has been set.
+    pub fn get_canonical_base(&self) -> Option<&str> {
+        self.canonical_base.as_deref()
+    }
+
+    /// Builds the canonical URL for the given path and query string, if a
+    /// [`canonical_base`](Self::canonical_base) has been set.
+    #[cfg_attr(not(feature = "meta"), allow(dead_code))]
+    pub(crate) fn canonical_url(
+        &self,
+        path: &str,
+        search: &str,
+    ) -> Option<String> {
+        let base = self.canonical_base.as_deref()?.trim_end_matches('/');
+        let mut url = format!("{base}{path}");
+        if self.canonical_include_search && !search.is_empty() {
+            url.push('?');
+            url.push_str(search);
+        }
+        Some(url)
+    }
+
+    /// Whether strict mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict_mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RouterConfig;
+    use crate::location::{ScrollBehavior, ScrollRestoration, TrailingSlash};
+
+    #[test]
+    fn builder_sets_options() {
+        let config = RouterConfig::new()
+            .base("/app")
+            .scroll_behavior(ScrollBehavior::Smooth)
+            .enforce_noopener(false)
+            .history_limit(5)
+            .scroll_restoration(ScrollRestoration { auto: true })
+            .strict_mode(true)
+            .trailing_slash(TrailingSlash::Drop);
+        assert_eq!(config.get_base().map(|base| &**base), Some("/app"));
+        assert_eq!(config.get_scroll_config().behavior, ScrollBehavior::Smooth);
+        assert!(!config.get_location_options().enforce_noopener);
+        assert_eq!(config.get_location_options().history_limit, 5);
+        assert!(config.get_scroll_restoration().auto);
+        assert!(config.is_strict());
+        assert_eq!(config.get_trailing_slash(), TrailingSlash::Drop);
+    }
+
+    #[test]
+    fn canonical_url() {
+        assert_eq!(RouterConfig::new().canonical_url("/a", ""), None);
+        let config = RouterConfig::new().canonical_base("https://leptos.dev/");
+        assert_eq!(
+            config.canonical_url("/posts", "page=2").as_deref(),
+            Some("https://leptos.dev/posts")
+        );
+        let config = config.canonical_include_search(true);
+        assert_eq!(
+            config.canonical_url("/posts", "page=2").as_deref(),
+            Some("https://leptos.dev/posts?page=2")
+        );
+    }
+
+    #[test]
+    fn defaults_match_location_options() {
+        let config = RouterConfig::default();
+        assert_eq!(config.get_base(), None);
+        assert_eq!(config.get_location_options(), &Default::default());
+        assert!(!config.is_strict());
+        assert!(!config.get_scroll_restoration().auto);
+        assert_eq!(config.get_trailing_slash(), TrailingSlash::Exact);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    hooks::Matched,
-    location::{LocationProvider, Url},
+    hooks::{report_route_match, Matched},
+    location::{LocationProvider, RouteError, Url},
     matching::{MatchParams, RouteDefs},
     params::ParamsMap,
     view_transition::start_view_transition,
@@ -39,6 +39,7 @@ pub(crate) struct FlatRoutesView<Loc, Defs, FalFn> {
     pub outer_owner: Owner,
     pub set_is_routing: Option<SignalSetter<bool>>,
     pub transition: bool,
+    pub route_error: ArcRwSignal<Option<RouteError>>,
 }
 
 /// Retained view state for the flat router.
@@ -90,12 +91,14 @@ where
             routes,
             fallback,
             outer_owner,
+            route_error,
             ..
         } = self;
         let current_url = current_url.read_untracked();
 
         // we always need to match the new route
         let new_match = routes.match_route(current_url.path());
+        report_route_match(&route_error, new_match.is_some());
         let id = new_match.as_ref().map(|n| n.as_id());
         let matched = ArcRwSignal::new(
             new_match
@@ -200,6 +203,7 @@ where
             outer_owner,
             set_is_routing,
             transition,
+            route_error,
         } = self;
         let url_snapshot = current_url.read_untracked();
 
@@ -220,6 +224,7 @@ where
 
         // otherwise, match the new route
         let new_match = routes.match_route(url_snapshot.path());
+        report_route_match(&route_error, new_match.is_some());
         let new_id = new_match.as_ref().map(|n| n.as_id());
         let matched_string = new_match
             .as_ref()
@@ -291,6 +296,9 @@ where
                     .as_ref()
                     .map(|nav| nav.is_back().get_untracked())
                     .unwrap_or(false);
+                let transition_state = location
+                    .as_ref()
+                    .and_then(|nav| nav.view_transition_state());
                 Executor::spawn_local(owner.with(|| {
                     ScopedFuture::new({
                         let state = Rc::clone(state);
@@ -323,7 +331,12 @@ where
                                         .rebuild(&mut state.borrow_mut().view);
                                 };
                                 if transition {
-                                    start_view_transition(0, is_back, rebuild);
+                                    start_view_transition(
+                                        0,
+                                        is_back,
+                                        transition_state,
+                                        rebuild,
+                                    );
                                 } else {
                                     rebuild();
                                 }
@@ -487,6 +500,7 @@ where
     fn choose_ssr(self) -> OwnedView<AnyView> {
         let current_url = self.current_url.read_untracked();
         let new_match = self.routes.match_route(current_url.path());
+        report_route_match(&self.route_error, new_match.is_some());
         let owner = self.outer_owner.child();
         let url = ArcRwSignal::new(current_url.to_owned());
         let params = ArcRwSignal::new(
@@ -648,12 +662,14 @@ where
             routes,
             fallback,
             outer_owner,
+            route_error,
             ..
         } = self;
         let current_url = current_url.read_untracked();
 
         // we always need to match the new route
         let new_match = routes.match_route(current_url.path());
+        report_route_match(&route_error, new_match.is_some());
         let id = new_match.as_ref().map(|n| n.as_id());
         let matched = ArcRwSignal::new(
             new_match
@@ -1,7 +1,7 @@
 use crate::{
     components::ToHref,
     hooks::{has_router, use_navigate, use_resolved_path},
-    location::{BrowserUrl, LocationProvider},
+    location::{BrowserUrl, LocationProvider, ROUTER_FORM_PROPERTY},
     NavigateOptions,
 };
 use leptos::{ev, html::form, logging::*, prelude::*, task::spawn_local};
@@ -192,6 +192,7 @@ where
                                                     ),
                                                     navigate_options,
                                                 )
+                                                .forget()
                                             }
                                         }
                                         Err(e) => warn!("{:?}", e),
@@ -268,6 +269,7 @@ where
                                                     ),
                                                     navigate_options,
                                                 )
+                                                .forget()
                                             }
                                         }
                                         Err(e) => warn!("{:?}", e),
@@ -303,6 +305,7 @@ where
             .attr("method", method)
             .attr("action", move || action.get())
             .attr("enctype", enctype)
+            .prop(ROUTER_FORM_PROPERTY, true)
             .on(ev::submit, on_submit)
             .child(children())
     }
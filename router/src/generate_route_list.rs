@@ -1,5 +1,7 @@
 use crate::{
-    matching::PathSegment,
+    location::Url,
+    matching::{ExpandOptionals, PathSegment},
+    params::ParamsMap,
     static_routes::{
         RegenerationFn, ResolvedStaticPath, StaticPath, StaticRoute,
     },
@@ -150,6 +152,136 @@ impl RouteListing {
     */
 }
 
+/// A single entry in a sitemap, as generated by [`RouteList::generate_sitemap`].
+///
+/// Only routes whose paths consist entirely of static segments produce an entry.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SitemapEntry {
+    /// The path of the route, including the router's base.
+    pub path: String,
+    /// How frequently the page is likely to change (e.g., `"daily"`).
+    pub changefreq: Option<String>,
+    /// The priority of this URL relative to other URLs on the site, from `0.0` to `1.0`.
+    pub priority: Option<f32>,
+}
+
+impl SitemapEntry {
+    /// Creates a sitemap entry for the given path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the change frequency for this entry.
+    pub fn with_changefreq(mut self, changefreq: impl Into<String>) -> Self {
+        self.changefreq = Some(changefreq.into());
+        self
+    }
+
+    /// Sets the priority for this entry.
+    pub fn with_priority(mut self, priority: f32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+/// Builds sitemap entries for each of the given route paths, skipping any path that contains a
+/// parameter or wildcard. Optional parameters are expanded, so that `/posts/:page?` still yields
+/// an entry for `/posts`.
+pub(crate) fn sitemap_entries<'a>(
+    paths: impl IntoIterator<Item = &'a [PathSegment]>,
+) -> Vec<SitemapEntry> {
+    let mut entries: Vec<SitemapEntry> = Vec::new();
+    for path in paths {
+        for segments in path.to_vec().expand_optionals() {
+            let Some(path) = static_path(&segments) else {
+                continue;
+            };
+            if !entries.iter().any(|entry| entry.path == path) {
+                entries.push(SitemapEntry::new(path));
+            }
+        }
+    }
+    entries
+}
+
+/// Builds a concrete path from a route's segments by filling in its params from the given map.
+/// Any params that are not used by the route are added to the path as a query string.
+///
+/// Returns the name of the first required param that is missing from the map as an error.
+pub(crate) fn generate_path(
+    segments: &[PathSegment],
+    mut params: ParamsMap,
+) -> Result<String, String> {
+    let mut path = String::new();
+    let mut push_segment = |segment: &str| {
+        let segment = segment.trim_matches('/');
+        if !segment.is_empty() {
+            path.push('/');
+            path.push_str(segment);
+        }
+    };
+    for segment in segments {
+        match segment {
+            PathSegment::Unit => {}
+            PathSegment::Static(s) => push_segment(s),
+            PathSegment::Param(name) => {
+                let value = params
+                    .remove(name)
+                    .and_then(|values| values.into_iter().last())
+                    .ok_or_else(|| name.to_string())?;
+                push_segment(&Url::escape(&value));
+            }
+            PathSegment::OptionalParam(name) => {
+                if let Some(value) = params
+                    .remove(name)
+                    .and_then(|values| values.into_iter().last())
+                {
+                    push_segment(&Url::escape(&value));
+                }
+            }
+            PathSegment::Splat(name) => {
+                if let Some(value) = params
+                    .remove(name)
+                    .and_then(|values| values.into_iter().last())
+                {
+                    push_segment(&value);
+                }
+            }
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    path.push_str(&params.to_query_string());
+    Ok(path)
+}
+
+fn static_path(segments: &[PathSegment]) -> Option<String> {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::Unit => {}
+            PathSegment::Static(s) => {
+                let s = s.trim_matches('/');
+                if !s.is_empty() {
+                    path.push('/');
+                    path.push_str(s);
+                }
+            }
+            PathSegment::Param(_)
+            | PathSegment::OptionalParam(_)
+            | PathSegment::Splat(_) => return None,
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    Some(path)
+}
+
 /// A set of routes generated from the route definitions.
 #[derive(Debug, Default, Clone)]
 pub struct RouteList(Vec<RouteListing>);
@@ -183,6 +315,12 @@ impl RouteList {
         self.0.iter()
     }
 
+    /// Generates a sitemap from the inner list of route listings, including only those routes
+    /// that do not contain any parameters or wildcards.
+    pub fn generate_sitemap(&self) -> Vec<SitemapEntry> {
+        sitemap_entries(self.iter().map(RouteListing::path))
+    }
+
     /// Generates a list of resolved static paths based on the inner list of route listings.
     pub async fn into_static_paths(self) -> Vec<ResolvedStaticPath> {
         futures::future::join_all(
@@ -255,3 +393,92 @@ impl RouteList {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{sitemap_entries, RouteList, RouteListing, SitemapEntry};
+    #[cfg(feature = "ssr")]
+    use crate::params::ParamsMap;
+    use crate::PathSegment;
+
+    fn paths(list: &[SitemapEntry]) -> Vec<&str> {
+        list.iter().map(|entry| entry.path.as_str()).collect()
+    }
+
+    #[test]
+    fn sitemap_includes_static_routes() {
+        let list = RouteList::from(vec![
+            RouteListing::from_path([PathSegment::Static("".into())]),
+            RouteListing::from_path([
+                PathSegment::Static("".into()),
+                PathSegment::Static("/about".into()),
+            ]),
+            RouteListing::from_path([
+                PathSegment::Static("/blog".into()),
+                PathSegment::Unit,
+                PathSegment::Static("archive".into()),
+            ]),
+        ]);
+        assert_eq!(
+            paths(&list.generate_sitemap()),
+            vec!["/", "/about", "/blog/archive"]
+        );
+    }
+
+    #[test]
+    fn sitemap_skips_params_and_wildcards() {
+        let list = RouteList::from(vec![
+            RouteListing::from_path([
+                PathSegment::Static("/post".into()),
+                PathSegment::Param("id".into()),
+            ]),
+            RouteListing::from_path([
+                PathSegment::Static("/files".into()),
+                PathSegment::Splat("rest".into()),
+            ]),
+        ]);
+        assert!(list.generate_sitemap().is_empty());
+    }
+
+    #[test]
+    fn sitemap_expands_optional_params() {
+        let path = vec![
+            PathSegment::Static("/posts".into()),
+            PathSegment::OptionalParam("page".into()),
+        ];
+        let entries = sitemap_entries([path.as_slice(), path.as_slice()]);
+        assert_eq!(paths(&entries), vec!["/posts"]);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn generate_path_fills_in_params() {
+        let segments = [
+            PathSegment::Static("/users".into()),
+            PathSegment::Param("id".into()),
+            PathSegment::OptionalParam("tab".into()),
+            PathSegment::Static("files".into()),
+            PathSegment::Splat("rest".into()),
+        ];
+        let params = [("id", "42"), ("rest", "a/b.txt"), ("sort", "asc")]
+            .into_iter()
+            .collect::<ParamsMap>();
+        assert_eq!(
+            super::generate_path(&segments, params),
+            Ok("/users/42/files/a/b.txt?sort=asc".to_string())
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn generate_path_requires_params() {
+        let segments = [
+            PathSegment::Static("/users".into()),
+            PathSegment::Param("id".into()),
+        ];
+        assert_eq!(
+            super::generate_path(&segments, ParamsMap::new()),
+            Err("id".to_string())
+        );
+    }
+}
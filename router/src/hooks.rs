@@ -1,21 +1,56 @@
 use crate::{
     components::RouterContext,
-    location::{Location, Url},
-    navigate::NavigateOptions,
-    params::{Params, ParamsError, ParamsMap},
+    generate_route_list::SitemapEntry,
+    link::is_path_active,
+    location::{
+        Location, LocationProvider, NavigationDirection, RouteError,
+        RouterError, State, Url,
+    },
+    navigate::{
+        NavigateOptions, NavigationDecision, NavigationGuardHandle,
+        NavigationInterceptor, NavigationOutcome, NavigationTarget,
+    },
+    params::{Params, ParamsError, ParamsMap, ParamsMergeStrategy},
+    sticky_query::StickyQueryConfig,
+    view_transition::ViewTransitionState,
+};
+use futures::{
+    channel::oneshot,
+    future::{select, Either},
+};
+use leptos::{
+    callback::{Callable, Callback},
+    leptos_dom::helpers::{
+        document, request_animation_frame, set_timeout_with_handle, window,
+    },
+    oco::Oco,
 };
-use leptos::{leptos_dom::helpers::request_animation_frame, oco::Oco};
 use reactive_graph::{
     computed::{ArcMemo, Memo},
-    owner::{expect_context, use_context},
-    signal::{ArcRwSignal, ReadSignal},
-    traits::{Get, GetUntracked, ReadUntracked, With, WriteValue},
-    wrappers::write::SignalSetter,
+    effect::Effect,
+    owner::{expect_context, on_cleanup, use_context, StoredValue},
+    signal::{ArcRwSignal, ReadSignal, RwSignal},
+    traits::{
+        Get, GetUntracked, GetValue, ReadUntracked, Set, Update, With,
+        WithUntracked, WriteValue,
+    },
+    wrappers::{read::Signal, write::SignalSetter},
 };
+use send_wrapper::SendWrapper;
 use std::{
+    borrow::Cow,
+    future::Future,
+    pin::pin,
+    rc::Rc,
     str::FromStr,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{BeforeUnloadEvent, HtmlElement, KeyboardEvent};
 
 /// See [`query_signal`].
 #[track_caller]
@@ -145,26 +180,189 @@ where
     (get, set)
 }
 
+/// Binds a URL query parameter to a string signal in both directions.
+///
+/// Returns a memo with the current value of `key` and a setter that navigates to the current URL
+/// with `key` set to the new value, or removed if it is `None`. Other query parameters are kept.
+/// The setter replaces the current history entry, rather than pushing a new one for every
+/// change, so it is suited to inputs like search boxes.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::hooks::use_query_signal;
+///
+/// #[component]
+/// pub fn Search() -> impl IntoView {
+///     let (search, set_search) = use_query_signal("q");
+///     view! {
+///         <input
+///             prop:value=move || search.get().unwrap_or_default()
+///             on:input=move |ev| {
+///                 let value = event_target_value(&ev);
+///                 set_search((!value.is_empty()).then_some(value))
+///             }
+///         />
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_query_signal(
+    key: &'static str,
+) -> (Memo<Option<String>>, impl Fn(Option<String>) + Clone) {
+    let (get, set) = query_signal_with_options::<String>(
+        key,
+        NavigateOptions {
+            replace: true,
+            ..Default::default()
+        },
+    );
+    (get, move |value| set.set(value))
+}
+
+/// Pagination state that is stored in a URL query parameter, created by [`use_pagination`].
+#[derive(Debug, Clone, Copy)]
+pub struct PaginationHandle {
+    /// The current page number, starting from `1`.
+    pub page: Memo<u32>,
+    /// The number of items shown on each page.
+    pub per_page: u32,
+    /// Moves to the next page.
+    pub next: Callback<()>,
+    /// Moves to the previous page.
+    pub prev: Callback<()>,
+    /// Moves to the given page.
+    pub go_to: Callback<u32>,
+    /// The total number of pages, if known. This should be set by the caller once it has loaded
+    /// its data; when it is set, navigation is clamped to the last page.
+    pub total_pages: RwSignal<Option<u32>>,
+}
+
+/// Creates pagination state that is stored in the given URL query parameter, so that the current
+/// page survives reloads and can be shared as a link.
+///
+/// The page defaults to `1` if the query parameter is missing or invalid. Moving between pages
+/// replaces the current entry in the history stack, rather than adding a new one.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::hooks::use_pagination;
+///
+/// #[component]
+/// pub fn Pager() -> impl IntoView {
+///     let pagination = use_pagination("page", 20);
+///
+///     view! {
+///         <button on:click=move |_| pagination.prev.run(())>"Previous"</button>
+///         <span>"Page " {move || pagination.page.get()}</span>
+///         <button on:click=move |_| pagination.next.run(())>"Next"</button>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_pagination(
+    page_param: impl Into<Oco<'static, str>>,
+    per_page: u32,
+) -> PaginationHandle {
+    let (query_page, set_query_page) = query_signal_with_options::<u32>(
+        page_param,
+        NavigateOptions {
+            replace: true,
+            ..Default::default()
+        },
+    );
+    let page =
+        Memo::new(move |_| query_page.get().filter(|n| *n > 0).unwrap_or(1));
+    let total_pages = RwSignal::new(None);
+
+    let go_to = Callback::new(move |to: u32| {
+        let last = total_pages.get_untracked().unwrap_or(u32::MAX).max(1);
+        set_query_page.set(Some(to.clamp(1, last)));
+    });
+    let next = Callback::new(move |_: ()| {
+        go_to.run(page.get_untracked().saturating_add(1))
+    });
+    let prev = Callback::new(move |_: ()| {
+        go_to.run(page.get_untracked().saturating_sub(1))
+    });
+
+    PaginationHandle {
+        page,
+        per_page,
+        next,
+        prev,
+        go_to,
+        total_pages,
+    }
+}
+
 #[track_caller]
 pub(crate) fn has_router() -> bool {
     use_context::<RouterContext>().is_some()
 }
 
-/*
-/// Returns the current [`RouterContext`], containing information about the router's state.
+/// A handle to the surrounding [`Router`](crate::components::Router), which can be used to
+/// navigate from anywhere inside it without depending on which location provider it uses.
+///
+/// This is returned by [`use_router`]. It is cheap to clone.
+#[derive(Clone, Debug)]
+pub struct RouterHandle(RouterContext);
+
+impl RouterHandle {
+    /// Navigates to `to`, like the function returned by [`use_navigate`].
+    pub fn navigate(
+        &self,
+        to: &str,
+        options: NavigateOptions,
+    ) -> NavigationOutcome {
+        self.0.navigate(to, options)
+    }
+
+    /// Navigates to `to`, replacing the current history entry rather than adding a new one.
+    pub fn replace(&self, to: &str) -> NavigationOutcome {
+        self.0.navigate(
+            to,
+            NavigateOptions {
+                replace: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Moves one step backward through the browser's session history. See [`use_router_back`].
+    pub fn back(&self) {
+        self.0.back();
+    }
+
+    /// Moves one step forward through the browser's session history. See
+    /// [`use_router_forward`].
+    pub fn forward(&self) {
+        self.0.forward();
+    }
+
+    /// Returns a signal that is `true` while a navigation is in progress. See
+    /// [`use_navigation_pending`].
+    pub fn is_navigating(&self) -> ReadSignal<bool> {
+        self.0.navigation_pending()
+    }
+}
+
+/// Returns a handle to the surrounding [`Router`](crate::components::Router).
+///
+/// # Panics
+/// Panics if called outside a `<Router/>`.
 #[track_caller]
-pub(crate) fn use_router() -> RouterContext {
-    if let Some(router) = use_context::<RouterContext>() {
-        router
-    } else {
-        leptos::leptos_dom::debug_warn!(
-            "You must call use_router() within a <Router/> component {:?}",
-            std::panic::Location::caller()
-        );
-        panic!("You must call use_router() within a <Router/> component");
+pub fn use_router() -> RouterHandle {
+    match use_context::<RouterContext>() {
+        Some(router) => RouterHandle(router),
+        None => {
+            leptos::leptos_dom::debug_warn!(
+                "You must call use_router() within a <Router/> component {:?}",
+                std::panic::Location::caller()
+            );
+            panic!("You must call use_router() within a <Router/> component");
+        }
     }
 }
-*/
 
 /// Returns the current [`Location`], which contains reactive variables
 #[track_caller]
@@ -174,6 +372,342 @@ pub fn use_location() -> Location {
     location
 }
 
+/// Returns the router's [`LocationProvider`], if it is of type `P`.
+///
+/// This can be used to call methods that are specific to one provider, like
+/// [`BrowserUrl::navigate_sync`](crate::location::BrowserUrl::navigate_sync). It returns `None`
+/// during server rendering, or if the router is using a different provider.
+#[track_caller]
+pub fn use_location_provider<P>() -> Option<Arc<P>>
+where
+    P: LocationProvider + Send + Sync,
+{
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the location provider outside a <Router>.");
+    router.get_provider()
+}
+
+/// Returns the stack of URLs that have been visited within the app, oldest first.
+///
+/// This can be used, for example, to show how many pages the user can go back. The stack is
+/// bounded by [`BrowserUrlOptions::history_limit`](crate::location::BrowserUrlOptions). During
+/// server rendering, it only contains the current URL.
+#[track_caller]
+pub fn use_history_stack() -> ReadSignal<Vec<Url>> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the history stack outside a <Router>.");
+    match router.location_provider {
+        Some(provider) => provider.path_stack.read_only().into(),
+        None => ArcRwSignal::new(vec![router.current_url.get_untracked()])
+            .read_only()
+            .into(),
+    }
+}
+
+/// Returns a typed view of the current [history
+/// state](https://developer.mozilla.org/en-US/docs/Web/API/History/state), and a function that
+/// replaces it.
+///
+/// The state is stored as a JSON string. The signal is `None` if there is no state, or if it
+/// cannot be deserialized into `T` (in which case a warning is logged). Setting the state
+/// replaces the current history entry, without changing the URL.
+#[cfg(feature = "serde")]
+#[track_caller]
+pub fn use_history_state<T>() -> (Signal<Option<T>>, impl Fn(T) + Clone)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the history state outside a <Router>.");
+    let state = router.location.state;
+    let value = Signal::derive(move || {
+        let json = state.with(|state| state.to_js_value().as_string())?;
+        serde_json::from_str(&json)
+            .inspect_err(|e| {
+                leptos::logging::warn!(
+                    "could not deserialize history state: {e}"
+                )
+            })
+            .ok()
+    });
+    let set_value = move |value: T| {
+        let json = match serde_json::to_string(&value) {
+            Ok(json) => json,
+            Err(e) => {
+                leptos::logging::error!(
+                    "could not serialize history state: {e}"
+                );
+                return;
+            }
+        };
+        let path = router.current_url.read_untracked().to_full_path();
+        router.navigate(
+            &path,
+            NavigateOptions {
+                resolve: false,
+                replace: true,
+                scroll: false,
+                state: State::new(Some(JsValue::from_str(&json))),
+                ..Default::default()
+            },
+        );
+    };
+    (value, set_value)
+}
+
+/// Returns a signal that is `true` while a client-side navigation is in progress: that is, from
+/// the time the URL changes until the new routes are ready to be shown.
+///
+/// This can be used, for example, to show a progress bar at the top of the page. During server
+/// rendering, it is always `false`.
+#[track_caller]
+pub fn use_navigation_pending() -> ReadSignal<bool> {
+    let router = use_context::<RouterContext>().expect(
+        "Tried to access the navigation pending state outside a <Router>.",
+    );
+    router.navigation_pending()
+}
+
+/// Returns a signal with the direction of the most recent navigation: whether it pushed a new
+/// history entry, replaced the current one, or went back.
+///
+/// This can be used, for example, to animate route transitions in the right direction. During
+/// server rendering, it is always [`NavigationDirection::Unknown`].
+#[track_caller]
+pub fn use_navigation_direction() -> ReadSignal<NavigationDirection> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the navigation direction outside a <Router>.");
+    match router.location_provider {
+        Some(provider) => provider.direction(),
+        None => ArcRwSignal::new(NavigationDirection::Unknown)
+            .read_only()
+            .into(),
+    }
+}
+
+/// Returns a signal that is `true` while the current navigation was triggered by going forward
+/// through the browser's history, as opposed to following a link or calling [`use_navigate`].
+///
+/// It is reset to `false` by the next navigation that is not a forward navigation. During server
+/// rendering, it is always `false`.
+#[track_caller]
+pub fn use_is_forward() -> ReadSignal<bool> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the navigation direction outside a <Router>.");
+    match router.location_provider {
+        Some(provider) => provider.is_forward(),
+        None => ArcRwSignal::new(false).read_only().into(),
+    }
+}
+
+/// Returns a signal with the number of entries in the browser's session history, like
+/// `window.history.length`, which updates after each navigation. This can be used to disable a
+/// "back" button when there is nothing to go back to.
+///
+/// During server rendering, it is always `1`.
+#[track_caller]
+pub fn use_history_length() -> Signal<u32> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the history length outside a <Router>.");
+    match router.location_provider {
+        Some(provider) => provider.history_length(),
+        None => Signal::derive(|| 1),
+    }
+}
+
+/// Returns a signal with the progress of the most recent view transition, which can be used to
+/// apply CSS classes while a transition is animating.
+///
+/// View transitions run when they are enabled with
+/// [`RouterConfig::use_view_transitions`](crate::RouterConfig::use_view_transitions) or with the
+/// `transition` prop on [`Routes`](crate::components::Routes). The state stays
+/// [`ViewTransitionState::Idle`] in browsers that do not support them, and during server
+/// rendering.
+#[track_caller]
+pub fn use_view_transition_state() -> ReadSignal<ViewTransitionState> {
+    let router = use_context::<RouterContext>().expect(
+        "Tried to access the view transition state outside a <Router>.",
+    );
+    match router.location_provider {
+        Some(provider) => provider.view_transition_state.read_only().into(),
+        None => ArcRwSignal::new(ViewTransitionState::Idle)
+            .read_only()
+            .into(),
+    }
+}
+
+/// Registers a guard that is called before each client-side navigation, and can allow, cancel,
+/// or redirect it.
+///
+/// Guards run in the order in which they were registered, and the first one that does not return
+/// [`NavigationDecision::Allow`] decides the outcome. This can be used, for example, to keep a
+/// user from leaving a form with unsaved changes, or to send them to a login page.
+///
+/// The guard is removed when the returned handle is dropped, so it should be kept alive for as
+/// long as it is needed (for example, by storing it in a [`StoredValue`](reactive_graph::owner::StoredValue)).
+/// Navigations that the browser handles itself, like pressing the back button, are not guarded.
+#[track_caller]
+pub fn use_before_navigate(
+    guard: impl Fn(NavigationTarget) -> NavigationDecision + Send + Sync + 'static,
+) -> NavigationGuardHandle {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to register a navigation guard outside a <Router>.");
+    router.guards.register(guard)
+}
+
+/// Registers a [`NavigationInterceptor`] that is called before each client-side navigation, for
+/// as long as the calling component is mounted.
+///
+/// Interceptors run in the order in which they were provided, before any guards registered with
+/// [`use_before_navigate`] or [`use_before_navigate_async`]. The first one that does not return
+/// [`InterceptorDecision::Continue`](crate::InterceptorDecision::Continue) decides the outcome.
+/// They run for navigations started by links, forms, and [`use_navigate`], but not for the
+/// browser's back and forward buttons.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{
+/// #     hooks::provide_navigation_interceptor, InterceptorDecision, NavigateOptions,
+/// # };
+/// # #[component] fn Demo() -> impl IntoView {
+/// provide_navigation_interceptor(|to: &str, _: &NavigateOptions| {
+///     leptos::logging::log!("navigating to {to}");
+///     InterceptorDecision::Continue
+/// });
+/// # }
+/// ```
+#[track_caller]
+pub fn provide_navigation_interceptor(interceptor: impl NavigationInterceptor) {
+    let router = use_context::<RouterContext>().expect(
+        "Tried to provide a navigation interceptor outside a <Router>.",
+    );
+    let id = router.guards.register_interceptor(interceptor);
+    on_cleanup(move || router.guards.remove_interceptor(id));
+}
+
+/// Keeps the given query parameters in the URL across client-side navigations.
+///
+/// While the calling component is mounted, the current values of these parameters (like
+/// `lang=fr` or `utm_source=newsletter`) are added to the URL of each navigation started by a
+/// link or with [`use_navigate`], unless the new URL already has its own value for them.
+#[track_caller]
+pub fn use_sticky_query(keys: &[&str]) {
+    use_sticky_query_with_config(keys, StickyQueryConfig::default())
+}
+
+/// Keeps the given query parameters in the URL across client-side navigations, like
+/// [`use_sticky_query`], with the given config.
+#[track_caller]
+pub fn use_sticky_query_with_config(keys: &[&str], config: StickyQueryConfig) {
+    let router = use_context::<RouterContext>().expect(
+        "Tried to register sticky query parameters outside a <Router>.",
+    );
+    let id = router
+        .sticky_query
+        .register(keys.iter().map(|key| key.to_string()), config);
+    on_cleanup(move || router.sticky_query.unregister(id));
+}
+
+/// Registers an async guard that is called before each client-side navigation, and can allow,
+/// cancel, or redirect it. This is useful for checks that need to wait on the network, like
+/// making sure a user's session is still valid before showing a protected route.
+///
+/// Async guards run in the same order as guards registered with [`use_before_navigate`], and the
+/// navigation waits for each one to finish. While it waits, the `set_is_routing` signal passed
+/// to the [`Router`](crate::components::Router) is set to `true`. If `timeout` is given and a
+/// guard takes longer than that, it is abandoned and the navigation is allowed.
+///
+/// The guard is removed when the returned handle is dropped.
+#[track_caller]
+pub fn use_before_navigate_async<Fut>(
+    guard: impl Fn(NavigationTarget) -> Fut + Send + Sync + 'static,
+    timeout: Option<Duration>,
+) -> NavigationGuardHandle
+where
+    Fut: Future<Output = NavigationDecision> + 'static,
+{
+    let router = use_context::<RouterContext>().expect(
+        "Tried to register an async navigation guard outside a <Router>.",
+    );
+    router.guards.register_async(move |target| {
+        let decision = guard(target);
+        Box::pin(async move {
+            let Some(timeout) = timeout else {
+                return decision.await;
+            };
+            let (tx, rx) = oneshot::channel::<()>();
+            let handle = set_timeout_with_handle(
+                move || {
+                    _ = tx.send(());
+                },
+                timeout,
+            );
+            match select(pin!(decision), rx).await {
+                Either::Left((decision, _)) => {
+                    if let Ok(handle) = handle {
+                        handle.clear();
+                    }
+                    decision
+                }
+                Either::Right((Ok(()), _)) => {
+                    leptos::logging::warn!(
+                        "Navigation guard timed out after {timeout:?}; \
+                         allowing navigation."
+                    );
+                    NavigationDecision::Allow
+                }
+                // the timeout could not be set, so just wait for the guard
+                Either::Right((Err(_), decision)) => decision.await,
+            }
+        })
+    })
+}
+
+/// Returns a function that generates a path to a named route, filling in its params.
+///
+/// Routes are named with the `name` prop on [`Route`](crate::components::Route) or
+/// [`ParentRoute`](crate::components::ParentRoute). Params that the route does not use are added
+/// to the path as query params. This returns an error if no route has the given name, or if a
+/// required param is missing.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{hooks::use_generate_path, params::ParamsMap};
+///
+/// #[component]
+/// pub fn UserLink(id: u64) -> impl IntoView {
+///     let generate_path = use_generate_path();
+///     let href = generate_path(
+///         "user",
+///         [("id", id.to_string())].into_iter().collect::<ParamsMap>(),
+///     )
+///     .unwrap_or_default();
+///
+///     view! { <a href=href>"Profile"</a> }
+/// }
+/// ```
+#[track_caller]
+pub fn use_generate_path(
+) -> impl Fn(&str, ParamsMap) -> Result<String, RouterError> + Clone {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to generate a route path outside a <Router>.");
+    move |name, params| router.generate_path(name, params)
+}
+
+/// Returns a sitemap entry for every static route that has been registered with the current
+/// [`Router`](crate::components::Router).
+///
+/// Routes with parameters or wildcards are skipped, as there is no way to know which values they
+/// should take. Routes are registered when a [`Routes`](crate::components::Routes) or
+/// [`FlatRoutes`](crate::components::FlatRoutes) component is created, so this should be called
+/// after the route tree has been rendered.
+#[track_caller]
+pub fn use_sitemap() -> Vec<SitemapEntry> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to generate a sitemap outside a <Router>.");
+    router.generate_sitemap()
+}
+
 pub(crate) type RawParamsMap = ArcMemo<ParamsMap>;
 
 #[track_caller]
@@ -189,7 +723,94 @@ pub fn use_params_map() -> Memo<ParamsMap> {
     use_params_raw().into()
 }
 
+/// Returns the params of the current route merged with the URL's query params, for components
+/// that accept a value from either place.
+///
+/// If a key is both a path param and a query param, `strategy` decides which value is kept.
+/// With the `tracing` feature enabled, each such conflict is logged at the `debug` level.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{hooks::use_merged_params, params::ParamsMergeStrategy};
+/// # fn fetch_users(page: Option<String>) {}
+/// #[component]
+/// fn Users() -> impl IntoView {
+///     // matches both `/users/page/:page` and `/users?page=2`
+///     let params = use_merged_params(ParamsMergeStrategy::PathWins);
+///     Effect::new(move |_| fetch_users(params.read().get("page")));
+/// }
+/// ```
+#[track_caller]
+pub fn use_merged_params(strategy: ParamsMergeStrategy) -> Memo<ParamsMap> {
+    let params = use_params_raw();
+    let query = use_query_map();
+    Memo::new(move |_| {
+        params.with(|path| query.with(|query| strategy.merge(path, query)))
+    })
+}
+
+/// Loads data for the current route, reloading it whenever the route's params change.
+///
+/// The `loader` is called with the params of the nearest matched route, and the
+/// [`Resource`](leptos::server::Resource) it returns can be read inside a `<Suspense/>` or
+/// `<Transition/>`. Like any other resource, the loader runs on the server during server
+/// rendering, and its result is serialized into the HTML so that it does not need to be loaded
+/// again while hydrating.
+///
+/// This requires the `serde` feature.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::hooks::use_route_data;
+/// # async fn fetch_post(id: Option<String>) -> String { String::new() }
+/// #[component]
+/// fn Post() -> impl IntoView {
+///     let post = use_route_data(|params| fetch_post(params.get("id")));
+///     view! {
+///         <Suspense>
+///             {move || Suspend::new(async move { post.await })}
+///         </Suspense>
+///     }
+/// }
+/// ```
+#[cfg(feature = "serde")]
+#[track_caller]
+pub fn use_route_data<T, Fut>(
+    loader: impl Fn(ParamsMap) -> Fut + Send + Sync + 'static,
+) -> leptos::server::Resource<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    Fut: Future<Output = T> + Send + 'static,
+{
+    let params = use_params_map();
+    leptos::server::Resource::new(move || params.get(), loader)
+}
+
 /// Returns the current route params, parsed into the given type, or an error.
+///
+/// `T` is usually a struct that derives [`Params`], in which each field implements [`FromStr`].
+/// The params are read from the nearest matched route, so a nested route sees the params of its
+/// own path segments along with those of its parents.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{hooks::use_params, params::Params};
+///
+/// #[derive(Params, PartialEq)]
+/// struct PostParams {
+///     id: Option<u64>,
+/// }
+///
+/// #[component]
+/// pub fn Post() -> impl IntoView {
+///     let params = use_params::<PostParams>();
+///     let id = move || {
+///         params.with(|params| params.as_ref().ok().and_then(|params| params.id))
+///     };
+///
+///     view! { <p>"Post " {id}</p> }
+/// }
+/// ```
 #[track_caller]
 pub fn use_params<T>() -> Memo<Result<T, ParamsError>>
 where
@@ -216,6 +837,20 @@ pub fn use_url() -> ReadSignal<Url> {
     use_url_raw().read_only().into()
 }
 
+/// Returns the current URL as an absolute URL string, including its origin, like
+/// `https://example.com/posts?page=2#comments`. The query string and hash are left out when they
+/// are empty.
+///
+/// This is useful for `<link rel="canonical">` and `og:url` meta tags, which must be absolute.
+/// During server rendering, the origin comes from the
+/// [`RequestOrigin`](crate::location::RequestOrigin) provided by the server integration, rather
+/// than from `window.location`.
+#[track_caller]
+pub fn use_canonical_url() -> Memo<String> {
+    let url = use_url_raw();
+    Memo::new(move |_| url.with(|url| url.to_string()))
+}
+
 /// Returns a raw key-value map of the URL search query.
 #[track_caller]
 pub fn use_query_map() -> Memo<ParamsMap> {
@@ -223,7 +858,24 @@ pub fn use_query_map() -> Memo<ParamsMap> {
     Memo::new(move |_| url.with(|url| url.search_params().clone()))
 }
 
+/// Returns the value of a single key in the URL search query.
+///
+/// Components that read the returned memo only rerun when this key's value changes, and not when
+/// other parts of the query string do.
+#[track_caller]
+pub fn use_query_param(
+    key: impl Into<Oco<'static, str>>,
+) -> Memo<Option<String>> {
+    let key = key.into();
+    let url = use_url_raw();
+    Memo::new(move |_| url.with(|url| url.search_params().get(&key)))
+}
+
 /// Returns the current URL search query, parsed into the given type, or an error.
+///
+/// `T` is usually a struct that derives [`Params`], in which each field implements [`FromStr`].
+/// If a field's value cannot be parsed, this returns [`ParamsError::InvalidParam`] with the name
+/// of the field and its raw value.
 #[track_caller]
 pub fn use_query<T>() -> Memo<Result<T, ParamsError>>
 where
@@ -236,6 +888,68 @@ where
 #[derive(Debug, Clone)]
 pub(crate) struct Matched(pub ArcMemo<String>);
 
+/// Information about one of the routes matched for the current URL, as passed to
+/// [`use_route_context_window`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// The portion of the URL's path matched by this route and all of its ancestors.
+    pub path: String,
+    /// The params matched by this route (not including those matched by its ancestors).
+    pub params: ParamsMap,
+    /// How many [`Outlet`](crate::components::Outlet) levels deep this route is; see
+    /// [`use_route_depth`].
+    pub depth: usize,
+}
+
+/// The chain of matched routes, from the outermost route down to the current one.
+#[derive(Debug, Clone)]
+pub(crate) struct RouteAncestry(pub ArcMemo<Vec<RouteInfo>>);
+
+impl RouteAncestry {
+    /// Creates the ancestry from the params and matched path signals of each route, outermost
+    /// first.
+    pub fn new(
+        levels: Vec<(ArcRwSignal<ParamsMap>, ArcRwSignal<String>)>,
+    ) -> Self {
+        Self(ArcMemo::new(move |_| {
+            let mut path = String::new();
+            levels
+                .iter()
+                .enumerate()
+                .map(|(depth, (params, matched))| {
+                    matched.with(|matched| path.push_str(matched));
+                    RouteInfo {
+                        path: path.clone(),
+                        params: params.get(),
+                        depth,
+                    }
+                })
+                .collect()
+        }))
+    }
+}
+
+/// The number of `<Outlet/>` levels between the `<Routes/>` and the current route.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RouteDepth(pub usize);
+
+/// Returns whether the current path matches `path`, for example to highlight a link in a custom
+/// navigation component. A relative `path` is resolved against the current route. See
+/// [`Location::is_active`].
+#[track_caller]
+pub fn use_is_active(path: impl Into<String>, exact: bool) -> Memo<bool> {
+    let path = path.into();
+    let href = use_resolved_path(move || path.clone());
+    let location = use_location();
+    Memo::new(move |_| {
+        href.with(|href| {
+            location
+                .pathname
+                .with(|pathname| is_path_active(href, pathname, exact))
+        })
+    })
+}
+
 /// Resolves the given path relative to the current route.
 #[track_caller]
 pub(crate) fn use_resolved_path(
@@ -260,24 +974,190 @@ pub(crate) fn use_resolved_path(
     })
 }
 
+/// Returns an `href` for `to`, resolved relative to the current URL's path and the router's base.
+///
+/// The memo updates whenever the current location or the value returned by `to` changes. Its
+/// value is a path from the root of the site, like `/app/users/42`, that can be used directly as
+/// the `href` of a plain `<a>` tag. Absolute URLs, like `https://leptos.dev`, are returned
+/// unchanged.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::hooks::use_href;
+/// # #[component] fn Demo() -> impl IntoView {
+/// let (id, _) = signal(42);
+/// // on `/app/users`, this is `/app/users/42`
+/// let href = use_href(move || id.get().to_string());
+/// view! { <a href=href>"Profile"</a> }
+/// # }
+/// ```
+#[track_caller]
+pub fn use_href(
+    to: impl Fn() -> String + Send + Sync + 'static,
+) -> Memo<String> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to call `use_href` outside a <Router>.");
+    let pathname = router.location.pathname;
+    Memo::new(move |_| {
+        let to = to();
+        pathname.with(|from| router.resolve_path(&to, Some(from)).into_owned())
+    })
+}
+
+/// Resolves `to` relative to the current route, taking the router's base into account. Absolute
+/// paths are returned unchanged. The memo updates when the route changes, or when `to` does if it
+/// is a signal.
+#[doc(hidden)]
+#[track_caller]
+pub fn use_resolve_path(to: impl Into<Signal<String>>) -> Memo<String> {
+    let to = to.into();
+    use_resolved_path(move || to.get()).into()
+}
+
 /// Returns a function that can be used to navigate to a new route.
 ///
 /// This should only be called on the client; it does nothing during
 /// server rendering.
 ///
+/// The function returns a [`NavigationOutcome`], which can be awaited to find out whether the
+/// navigation happened. The navigation happens whether or not it is awaited.
+///
+/// ```rust
+/// # if false { // can't actually navigate, no <Router/>
+/// let navigate = leptos_router::hooks::use_navigate();
+/// navigate("/", Default::default()).forget();
+/// # }
+/// ```
+///
+/// The path is taken as a `&str`, so an owned `String` can be passed by reference:
+///
 /// ```rust
 /// # if false { // can't actually navigate, no <Router/>
 /// let navigate = leptos_router::hooks::use_navigate();
-/// navigate("/", Default::default());
+/// let path = format!("/users/{}", 42);
+/// navigate(&path, Default::default()).forget();
 /// # }
 /// ```
+///
+/// ```rust
+/// # use leptos_router::{hooks::use_navigate, NavigationError};
+/// async fn go_home() -> Result<(), NavigationError> {
+///     let navigate = use_navigate();
+///     navigate("/", Default::default()).await?;
+///     Ok(())
+/// }
+/// ```
 #[track_caller]
-pub fn use_navigate() -> impl Fn(&str, NavigateOptions) + Clone {
+pub fn use_navigate(
+) -> impl Fn(&str, NavigateOptions) -> NavigationOutcome + Clone {
     let cx = use_context::<RouterContext>()
         .expect("You cannot call `use_navigate` outside a <Router>.");
     move |path: &str, options: NavigateOptions| cx.navigate(path, options)
 }
 
+/// Returns a function that navigates like the one returned by [`use_navigate`], but can safely be
+/// called from the callback of a [`Resource`](leptos::server::Resource) or an `Action`, after the future has resolved.
+///
+/// The navigation is deferred to the next animation frame, so that it runs outside of any
+/// reactive tracking. If the component that called this hook has been unmounted by then, the
+/// navigation is skipped instead of panicking.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::hooks::use_deferred_navigate;
+/// # async fn save() {}
+/// # #[component] fn Demo() -> impl IntoView {
+/// let navigate = use_deferred_navigate();
+/// let save_action = Action::new_local(move |_: &()| {
+///     let navigate = navigate.clone();
+///     async move {
+///         save().await;
+///         navigate("/saved", Default::default());
+///     }
+/// });
+/// # }
+/// ```
+#[track_caller]
+pub fn use_deferred_navigate() -> impl Fn(&str, NavigateOptions) + Clone {
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_deferred_navigate` outside a <Router>.");
+    // disposed along with the calling component's owner
+    let router = StoredValue::new(cx);
+    move |path: &str, options: NavigateOptions| {
+        let path = path.to_string();
+        request_animation_frame(move || {
+            if let Some(cx) = router.try_get_value() {
+                cx.navigate(&path, options).forget();
+            }
+        });
+    }
+}
+
+/// Returns a function that moves `delta` steps through the browser's session history, backward
+/// if `delta` is negative and forward if it is positive, like `history.go(delta)`.
+///
+/// The router handles the resulting `popstate` event like any other back or forward navigation,
+/// updating the current URL and [`use_navigation_direction`]. The function does nothing during
+/// server rendering.
+#[track_caller]
+pub fn use_router_go() -> impl Fn(i32) + Clone {
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_router_go` outside a <Router>.");
+    move |delta: i32| cx.go(delta)
+}
+
+/// Returns a function that goes one step back through the browser's session history. See
+/// [`use_router_go`].
+#[track_caller]
+pub fn use_router_back() -> impl Fn() + Clone {
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_router_back` outside a <Router>.");
+    move || cx.back()
+}
+
+/// Returns a function that goes one step forward through the browser's session history. See
+/// [`use_router_go`].
+#[track_caller]
+pub fn use_router_forward() -> impl Fn() + Clone {
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_router_forward` outside a <Router>.");
+    move || cx.forward()
+}
+
+/// Returns a function that tears down the router's route tree and renders it again from scratch,
+/// for the current URL. This can be used from the router's `error_boundary` fallback, for
+/// example in a "Retry" button.
+#[track_caller]
+pub fn use_router_reload() -> impl Fn() + Clone {
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_router_reload` outside a <Router>.");
+    move || cx.reload()
+}
+
+/// Returns a function that replaces the URL and state of the current history entry without
+/// navigating, so that routes are not matched again. See [`RouterContext::replace_state`].
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::hooks::{use_location, use_replace_state};
+/// # use wasm_bindgen::JsValue;
+/// # #[component] fn Demo() -> impl IntoView {
+/// let replace_state = use_replace_state();
+/// let location = use_location();
+/// let save_scroll = move |y: f64| {
+///     let url = location.pathname.get_untracked();
+///     _ = replace_state(&url, JsValue::from_f64(y).into());
+/// };
+/// # }
+/// ```
+#[track_caller]
+pub fn use_replace_state() -> impl Fn(&str, State) -> Result<(), JsValue> + Clone
+{
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_replace_state` outside a <Router>.");
+    move |url: &str, state: State| cx.replace_state(url, state)
+}
+
 /// Returns a reactive string that contains the route that was matched for
 /// this [`Route`](crate::components::Route).
 #[track_caller]
@@ -287,3 +1167,363 @@ pub fn use_matched() -> Memo<String> {
         .0
         .into()
 }
+
+/// Returns how many [`Outlet`](crate::components::Outlet) levels deep the current route is.
+///
+/// Routes rendered directly by [`Routes`](crate::components::Routes) or
+/// [`FlatRoutes`](crate::components::FlatRoutes) have a depth of `0`, their children a depth of
+/// `1`, and so on. Outside of any route, this also returns `0`.
+#[track_caller]
+pub fn use_route_depth() -> usize {
+    use_context::<RouteDepth>()
+        .map(|depth| depth.0)
+        .unwrap_or_default()
+}
+
+/// The titles registered with [`use_route_title`], as `(depth, id, title)`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RouteTitles {
+    next_id: Arc<AtomicUsize>,
+    titles: ArcRwSignal<Vec<(usize, usize, String)>>,
+}
+
+impl RouteTitles {
+    /// The title of the most deeply nested route, or of the most recently registered title if
+    /// there is more than one at that depth.
+    pub fn current(&self) -> Option<String> {
+        self.titles.with(|titles| {
+            titles
+                .iter()
+                .max_by_key(|(depth, id, _)| (*depth, *id))
+                .map(|(_, _, title)| title.clone())
+        })
+    }
+}
+
+/// Sets the document's title while the component that calls this hook is mounted, updating it
+/// whenever `title` changes.
+///
+/// If more than one component sets a title, the one in the most deeply nested route wins, so a
+/// layout can set a default title that the pages inside it override. When the component that set
+/// the current title is unmounted, the next title in line is used.
+///
+/// This only updates `document.title` in the browser. To render a `<title>` during server
+/// rendering, use the `<Title/>` component from `leptos_meta`.
+#[track_caller]
+pub fn use_route_title(title: impl Fn() -> String + 'static) {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to set a route title outside a <Router>.");
+    let RouteTitles { next_id, titles } = router.route_titles;
+    let id = next_id.fetch_add(1, Ordering::Relaxed);
+    let depth = use_route_depth();
+
+    Effect::new({
+        let titles = titles.clone();
+        move |_| {
+            let title = title();
+            titles.update(|titles| {
+                match titles.iter_mut().find(|(_, entry, _)| *entry == id) {
+                    Some(entry) => entry.2 = title,
+                    None => titles.push((depth, id, title)),
+                }
+            });
+        }
+    });
+    on_cleanup(move || {
+        titles.update(|titles| titles.retain(|(_, entry, _)| *entry != id));
+    });
+}
+
+/// Sets `document.title` to the result of `title`, updating it whenever the signals it reads
+/// change.
+///
+/// Inside a [`Router`](crate::components::Router), this works like [`use_route_title`]: if more
+/// than one component sets a title, the one in the most deeply nested route wins, and the title
+/// falls back to the next one in line when that component is unmounted. Outside a router, the
+/// most recently run title wins.
+///
+/// While server rendering with the `meta` feature enabled, the title is also registered with
+/// `leptos_meta`, so that the server integration renders it as a `<title>` tag in the page's
+/// `<head>`. Without the `meta` feature, nothing is rendered on the server.
+#[track_caller]
+pub fn use_document_title(title: impl Fn() -> String + 'static) {
+    #[cfg(all(feature = "ssr", feature = "meta"))]
+    if use_context::<leptos_meta::ServerMetaContext>().is_some() {
+        // registering a <Title/> pushes its text onto the server's title stack; the view itself
+        // is not needed, because the integration renders the <title> tag separately
+        _ = leptos_meta::Title(
+            leptos_meta::TitleProps::builder().text(title()).build(),
+        );
+        return;
+    }
+
+    if use_context::<RouterContext>().is_some() {
+        use_route_title(title);
+    } else {
+        Effect::new(move |_| document().set_title(&title()));
+    }
+}
+
+/// One level of the breadcrumb trail returned by [`use_breadcrumbs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreadcrumbItem {
+    /// The `title` of the matched route.
+    pub label: String,
+    /// The path matched by the route and its parents, including the router's base.
+    pub href: String,
+}
+
+/// Returns the breadcrumb trail for the current URL: an item for each matched route that has a
+/// `title`, from the outermost route to the innermost.
+///
+/// The trail is updated on every navigation. Only routes matched by
+/// [`Routes`](crate::components::Routes) are included, and routes displayed in named outlets are
+/// left out.
+#[track_caller]
+pub fn use_breadcrumbs() -> ReadSignal<Vec<BreadcrumbItem>> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access breadcrumbs outside a <Router>.");
+    router.breadcrumbs.read_only().into()
+}
+
+/// Returns the error that prevented the current route from being displayed, if there is one.
+///
+/// This is [`RouteError::NotFound`] whenever no route matches the current URL. If the
+/// [`Router`](crate::components::Router) has an `error_boundary`, it is also the first error
+/// thrown by a route that was not caught by an `<ErrorBoundary/>` inside the router. It is reset
+/// to `None` whenever a navigation matches a route.
+#[track_caller]
+pub fn use_route_error() -> ReadSignal<Option<RouteError>> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the route error outside a <Router>.");
+    router.route_error.read_only().into()
+}
+
+/// Returns the base path of the current [`Router`](crate::components::Router), if it has one.
+///
+/// This is the router's `base` prop, or the path of the page's `<base href>` element in the
+/// browser. It can be used to build links that include the base without hard-coding it. Relative
+/// links in [`A`](crate::components::A) are already resolved against it.
+#[track_caller]
+pub fn use_router_base() -> ReadSignal<Option<Cow<'static, str>>> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the router base outside a <Router>.");
+    router.base.read_only().into()
+}
+
+/// Returns the error from the most recent attempt to update the browser's history, if it failed.
+///
+/// Browsers can refuse to update the history, for example by throwing a `QuotaExceededError`
+/// when the history state is too large, or a `SecurityError` when the new URL is not
+/// same-origin with the document. The router reports these as [`RouterError::History`] instead
+/// of panicking; after a `SecurityError`, it also falls back to a full-page navigation.
+///
+/// This is reset to `None` by the next navigation that updates the history successfully.
+#[track_caller]
+pub fn use_router_error() -> ReadSignal<Option<RouterError>> {
+    let router = use_context::<RouterContext>()
+        .expect("Tried to access the router error outside a <Router>.");
+    router.router_error.read_only().into()
+}
+
+/// Records whether the router found a route for the current URL: a failed match is reported as
+/// [`RouteError::NotFound`], and a successful one clears the previous route's error.
+pub(crate) fn report_route_match(
+    route_error: &ArcRwSignal<Option<RouteError>>,
+    matched: bool,
+) {
+    let error = (!matched).then_some(RouteError::NotFound);
+    let changed = route_error.with_untracked(|current| {
+        !matches!(
+            (current, &error),
+            (None, None)
+                | (Some(RouteError::NotFound), Some(RouteError::NotFound))
+        )
+    });
+    if changed {
+        route_error.set(error);
+    }
+}
+
+/// Returns the `n`th segment (starting from `0`) of the current URL's path, or `None` if the path
+/// has fewer segments than that.
+///
+/// For example, for the path `/users/42/posts`, segment `1` is `"42"`.
+#[track_caller]
+pub fn use_route_segment(n: usize) -> Memo<Option<String>> {
+    let location = use_location();
+    Memo::new(move |_| {
+        location.pathname.with(|path| {
+            path.split('/')
+                .filter(|segment| !segment.is_empty())
+                .nth(n)
+                .map(ToOwned::to_owned)
+        })
+    })
+}
+
+/// Maps a window of the innermost `window_size` routes matched for the current URL (ending with
+/// the current route) into some value, which updates reactively on navigation.
+///
+/// This is useful for UI like breadcrumbs or timelines that only need a few of the current
+/// route's ancestors. If there are fewer than `window_size` matched routes, all of them are passed
+/// to `window_fn`; outside of any route, it receives an empty slice.
+#[track_caller]
+pub fn use_route_context_window<T>(
+    window_size: usize,
+    window_fn: impl Fn(&[RouteInfo]) -> T + Send + Sync + 'static,
+) -> Memo<T>
+where
+    T: PartialEq + Send + Sync + 'static,
+{
+    let ancestry = use_context::<RouteAncestry>();
+    Memo::new(move |_| match &ancestry {
+        Some(ancestry) => ancestry.0.with(|routes| {
+            let start = routes.len().saturating_sub(window_size);
+            window_fn(&routes[start..])
+        }),
+        None => window_fn(&[]),
+    })
+}
+
+/// Warns the user before they leave the page by closing the tab, reloading, or navigating to
+/// another site, for example when a form has unsaved changes.
+///
+/// Whenever the browser fires a `beforeunload` event, `message` is called. If it returns `Some`,
+/// the browser asks the user to confirm that they want to leave. (Most browsers show their own
+/// generic message rather than the one returned.) Client-side navigations within the app do not
+/// fire `beforeunload`; to guard those as well, use [`use_before_navigate`].
+///
+/// The listener is removed when the component that called this hook is unmounted. This does
+/// nothing during server rendering.
+#[track_caller]
+pub fn use_before_unload(message: impl Fn() -> Option<String> + 'static) {
+    let message = Rc::new(message);
+    Effect::new(move |_| {
+        let listener = Closure::wrap(Box::new({
+            let message = Rc::clone(&message);
+            move |ev: BeforeUnloadEvent| {
+                if let Some(message) = message() {
+                    ev.prevent_default();
+                    ev.set_return_value(&message);
+                }
+            }
+        })
+            as Box<dyn Fn(BeforeUnloadEvent)>)
+        .into_js_value();
+        let window = window();
+        window
+            .add_event_listener_with_callback(
+                "beforeunload",
+                listener.as_ref().unchecked_ref(),
+            )
+            .expect("couldn't add `beforeunload` listener to `window`");
+
+        // effects only run in the browser, so this is never sent across threads
+        let listener = SendWrapper::new((window, listener));
+        on_cleanup(move || {
+            let (window, listener) = listener.take();
+            _ = window.remove_event_listener_with_callback(
+                "beforeunload",
+                listener.as_ref().unchecked_ref(),
+            );
+        });
+    });
+}
+
+const FOCUSABLE_SELECTOR: &str =
+    "a[href], area[href], button:not([disabled]), \
+                                  input:not([disabled]), \
+                                  select:not([disabled]), \
+                                  textarea:not([disabled]), iframe, \
+                                  [contenteditable], \
+                                  [tabindex]:not([tabindex='-1'])";
+
+/// Traps keyboard focus inside the element matching `container_selector` while `active` is
+/// `true`.
+///
+/// This is intended for modals or drawers that are opened by navigating to a route: while the
+/// trap is active, `Tab` and `Shift+Tab` cycle through the focusable elements inside the container
+/// rather than moving focus to the rest of the page. When `active` becomes `false`, or the
+/// component that called this hook is unmounted, focus is returned to whichever element was
+/// focused before the trap was activated.
+#[track_caller]
+pub fn use_focus_trap(active: ReadSignal<bool>, container_selector: &str) {
+    let container_selector = container_selector.to_string();
+    Effect::new(move |_| {
+        if !active.get() {
+            return;
+        }
+
+        let document = document();
+        let previous = document.active_element();
+        let listener = Closure::wrap(Box::new({
+            let container_selector = container_selector.clone();
+            move |ev: KeyboardEvent| trap_focus(&container_selector, &ev)
+        }) as Box<dyn Fn(KeyboardEvent)>)
+        .into_js_value();
+        document
+            .add_event_listener_with_callback(
+                "keydown",
+                listener.as_ref().unchecked_ref(),
+            )
+            .expect("couldn't add `keydown` listener to `document`");
+
+        // effects only run in the browser, so this is never sent across threads
+        let trap = SendWrapper::new((document, listener, previous));
+        on_cleanup(move || {
+            let (document, listener, previous) = trap.take();
+            _ = document.remove_event_listener_with_callback(
+                "keydown",
+                listener.as_ref().unchecked_ref(),
+            );
+            if let Some(previous) =
+                previous.and_then(|el| el.dyn_into::<HtmlElement>().ok())
+            {
+                _ = previous.focus();
+            }
+        });
+    });
+}
+
+fn trap_focus(container_selector: &str, ev: &KeyboardEvent) {
+    if ev.key() != "Tab" {
+        return;
+    }
+
+    let document = document();
+    let Some(container) =
+        document.query_selector(container_selector).ok().flatten()
+    else {
+        return;
+    };
+    let Ok(candidates) = container.query_selector_all(FOCUSABLE_SELECTOR)
+    else {
+        return;
+    };
+    let focusable = (0..candidates.length())
+        .filter_map(|idx| candidates.item(idx))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .collect::<Vec<_>>();
+    let (Some(first), Some(last)) = (focusable.first(), focusable.last())
+    else {
+        // nothing to focus inside the container, so keep focus where it is
+        ev.prevent_default();
+        return;
+    };
+
+    let current = document.active_element();
+    let is_current = |el: &HtmlElement| current.as_ref() == Some(&**el);
+    let inside = current
+        .as_ref()
+        .is_some_and(|current| container.contains(Some(current.as_ref())));
+    let wrap_to = if ev.shift_key() {
+        (!inside || is_current(first)).then_some(last)
+    } else {
+        (!inside || is_current(last)).then_some(first)
+    };
+    if let Some(el) = wrap_to {
+        ev.prevent_default();
+        _ = el.focus();
+    }
+}
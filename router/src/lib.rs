@@ -115,6 +115,8 @@
 //! - `ssr` Server-side rendering: Generate an HTML string (typically on the server)
 //! - `nightly`: On `nightly` Rust, enables the function-call syntax for signal getters and setters.
 //! - `tracing`: Enables support for the `tracing` crate.
+//! - `same-site`: Enables [`Url::is_same_site`](crate::location::Url::is_same_site), which
+//!   bundles a small list of public domain suffixes.
 //!
 //! [`Leptos`]: <https://github.com/leptos-rs/leptos>
 //! [`router`]: <https://github.com/leptos-rs/leptos/blob/main/examples/router/src/lib.rs>
@@ -126,6 +128,7 @@
 
 /// Components for route definition and for enhanced links and forms.
 pub mod components;
+mod config;
 /// An optimized "flat" router without nested routes.
 pub mod flat_router;
 mod form;
@@ -145,7 +148,9 @@ pub mod params;
 mod ssr_mode;
 /// Support for static routing.
 pub mod static_routes;
+mod sticky_query;
 
+pub use config::*;
 pub use generate_route_list::*;
 #[doc(inline)]
 pub use leptos_router_macro::path;
@@ -153,71 +158,105 @@ pub use matching::*;
 pub use method::*;
 pub use navigate::*;
 pub use ssr_mode::*;
+pub use sticky_query::*;
+pub use view_transition::ViewTransitionState;
 
 pub(crate) mod view_transition {
     use js_sys::{Function, Promise, Reflect};
     use leptos::leptos_dom::helpers::document;
+    use reactive_graph::{signal::ArcRwSignal, traits::Set};
     use wasm_bindgen::{closure::Closure, intern, JsCast, JsValue};
 
+    /// The progress of the current [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API).
+    /// See [`use_view_transition_state`](crate::hooks::use_view_transition_state).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum ViewTransitionState {
+        /// No view transition has run yet, or the browser does not support them.
+        #[default]
+        Idle,
+        /// A view transition is animating.
+        Animating,
+        /// The most recent view transition has finished.
+        Finished,
+    }
+
     pub fn start_view_transition(
         level: u8,
         is_back_navigation: bool,
+        state: Option<ArcRwSignal<ViewTransitionState>>,
         fun: impl FnOnce() + 'static,
     ) {
-        let document = document();
-        let document_element = document.document_element().unwrap();
+        let document_element = document().document_element().unwrap();
         let class_list = document_element.class_list();
+        _ = class_list.add_1(&format!("router-outlet-{level}"));
+        if is_back_navigation {
+            _ = class_list.add_1("router-back");
+        }
+        let supported = run_view_transition(state, fun, move || {
+            if is_back_navigation {
+                class_list.remove_1("router-back").unwrap();
+            }
+            class_list
+                .remove_1(&format!("router-outlet-{level}"))
+                .unwrap();
+        });
+        if !supported {
+            leptos::logging::warn!(
+                "NOTE: View transitions are not supported in this browser; \
+                 unless you provide a polyfill, view transitions will not be \
+                 applied."
+            );
+        }
+    }
+
+    /// Runs `fun` inside `document.startViewTransition()`, and calls `on_finished` once the
+    /// transition has finished.
+    ///
+    /// If the browser does not support view transitions, `fun` is called immediately instead,
+    /// `on_finished` is never called, and this returns `false`.
+    pub fn run_view_transition(
+        state: Option<ArcRwSignal<ViewTransitionState>>,
+        fun: impl FnOnce() + 'static,
+        on_finished: impl FnOnce() + 'static,
+    ) -> bool {
+        let document = document();
         let svt = Reflect::get(
             &document,
             &JsValue::from_str(intern("startViewTransition")),
         )
         .and_then(|svt| svt.dyn_into::<Function>());
-        _ = class_list.add_1(&format!("router-outlet-{level}"));
-        if is_back_navigation {
-            _ = class_list.add_1("router-back");
-        }
-        match svt {
-            Ok(svt) => {
-                let cb = Closure::once_into_js(Box::new(move || {
-                    fun();
-                }));
-                match svt.call1(
-                    document.unchecked_ref(),
-                    cb.as_ref().unchecked_ref(),
-                ) {
-                    Ok(view_transition) => {
-                        let class_list = document_element.class_list();
-                        let finished = Reflect::get(
-                            &view_transition,
-                            &JsValue::from_str("finished"),
-                        )
-                        .expect("no `finished` property on ViewTransition")
-                        .unchecked_into::<Promise>();
-                        let cb = Closure::new(Box::new(move |_| {
-                            if is_back_navigation {
-                                class_list.remove_1("router-back").unwrap();
-                            }
-                            class_list
-                                .remove_1(&format!("router-outlet-{level}"))
-                                .unwrap();
-                        })
-                            as Box<dyn FnMut(JsValue)>);
-                        _ = finished.then(&cb);
-                        cb.into_js_value();
-                    }
-                    Err(e) => {
-                        web_sys::console::log_1(&e);
-                    }
+        let Ok(svt) = svt else {
+            fun();
+            return false;
+        };
+
+        let cb = Closure::once_into_js(Box::new(move || {
+            fun();
+        }));
+        match svt.call1(document.unchecked_ref(), cb.as_ref().unchecked_ref()) {
+            Ok(view_transition) => {
+                if let Some(state) = &state {
+                    state.set(ViewTransitionState::Animating);
                 }
+                let finished = Reflect::get(
+                    &view_transition,
+                    &JsValue::from_str("finished"),
+                )
+                .expect("no `finished` property on ViewTransition")
+                .unchecked_into::<Promise>();
+                let cb = Closure::once(move |_: JsValue| {
+                    on_finished();
+                    if let Some(state) = state {
+                        state.set(ViewTransitionState::Finished);
+                    }
+                });
+                _ = finished.then(&cb);
+                cb.into_js_value();
             }
-            Err(_) => {
-                leptos::logging::warn!(
-                    "NOTE: View transitions are not supported in this \
-                     browser; unless you provide a polyfill, view transitions \
-                     will not be applied."
-                );
-                fun();
+            Err(e) => {
+                web_sys::console::log_1(&e);
             }
         }
+        true
     }
 }
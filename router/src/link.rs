@@ -144,6 +144,26 @@ where
     inner(href, target, exact, children, strict_trailing_slash, scroll)
 }
 
+/// Tests whether `href` is active for `location`, ignoring any query string or hash in `href`.
+///
+/// If `exact` is `true`, the paths must match, apart from a trailing slash. Otherwise `location`
+/// may also be nested inside `href`.
+pub(crate) fn is_path_active(href: &str, location: &str, exact: bool) -> bool {
+    fn trim_slash(path: &str) -> &str {
+        match path.trim_end_matches('/') {
+            "" => "/",
+            path => path,
+        }
+    }
+
+    let href = href.split(['?', '#']).next().unwrap_or_default();
+    if exact {
+        trim_slash(href) == trim_slash(location)
+    } else {
+        is_active_for(href, location, false)
+    }
+}
+
 // Test if `href` is active for `location`.  Assumes _both_ `href` and `location` begin with a `'/'`.
 fn is_active_for(
     href: &str,
@@ -173,7 +193,17 @@ fn is_active_for(
 
 #[cfg(test)]
 mod tests {
-    use super::is_active_for;
+    use super::{is_active_for, is_path_active};
+
+    #[test]
+    fn is_path_active_ignores_trailing_slash_and_query() {
+        assert!(is_path_active("/item/", "/item", true));
+        assert!(is_path_active("/item?page=2", "/item/", true));
+        assert!(is_path_active("/", "/", true));
+        assert!(!is_path_active("/item", "/item/one", true));
+        assert!(is_path_active("/item", "/item/one", false));
+        assert!(!is_path_active("/items", "/item", false));
+    }
 
     #[test]
     fn is_active_for_matched() {
@@ -1,32 +1,134 @@
-use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
-use crate::{hooks::use_navigate, params::ParamsMap};
+use super::scroll::{OverrideScroll, ScrollConfig, ScrollRestoration};
+use super::{
+    handle_anchor_click, handle_anchor_hover, handle_form_submit,
+    LocationChange, LocationProvider, NavigationDirection, RouterError, State,
+    Url,
+};
+use crate::{
+    hooks::use_navigate,
+    navigate::{
+        Debouncer, NavigateOptions, NavigationDecision, NavigationGuards,
+        NavigationTarget,
+    },
+    params::ParamsMap,
+    sticky_query::StickyQuery,
+    view_transition::{run_view_transition, ViewTransitionState},
+    RouterConfig,
+};
 use core::fmt;
-use futures::channel::oneshot;
-use js_sys::{try_iter, Array, JsString};
+use futures::{
+    channel::oneshot,
+    future::{self, Either},
+};
+use js_sys::{try_iter, Array, JsString, Object, Reflect};
 use leptos::prelude::*;
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
     signal::ArcRwSignal,
-    traits::{ReadUntracked, Set},
+    traits::{ReadUntracked, Set, WithUntracked},
 };
+use send_wrapper::SendWrapper;
 use std::{
     borrow::Cow,
     boxed::Box,
+    mem,
     string::String,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 use tachys::dom::{document, window};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-use web_sys::{Event, UrlSearchParams};
+use web_sys::{
+    CustomEvent, CustomEventInit, Event, PopStateEvent, UrlSearchParams,
+};
+
+/// The key under which [`BrowserUrl`] saves the scroll position in `history.state`.
+const SCROLL_POSITION_KEY: &str = "__leptos_scroll";
+
+/// Options that configure the behavior of a [`BrowserUrl`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserUrlOptions {
+    /// If `true`, a cancelable `leptos:navigate`
+    /// [`CustomEvent`](https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent) is
+    /// dispatched on `window` each time a navigation completes, with the previous and new paths
+    /// as the `from` and `to` properties of its `detail`. This allows code outside of Leptos to
+    /// react to route changes. Canceling the event does not roll back the navigation.
+    ///
+    /// Defaults to `false`.
+    pub dispatch_navigation_event: bool,
+    /// The maximum number of entries kept in the router's in-app history stack (see
+    /// [`use_history_stack`](crate::hooks::use_history_stack)). Once the limit is reached, the
+    /// oldest entries are discarded.
+    ///
+    /// Defaults to `100`.
+    pub history_limit: usize,
+    /// If `true`, clicking a link with `target="_blank"` that does not already have
+    /// `rel="noopener"` adds `rel="noopener noreferrer"` to it before the browser opens it, so
+    /// that the new page cannot access this one through `window.opener`.
+    ///
+    /// Defaults to `true`.
+    pub enforce_noopener: bool,
+    /// If set, each navigation waits for this long before it happens, and is dropped if another
+    /// navigation starts in the meantime. This applies to clicks on links, and to navigations
+    /// started with [`use_navigate`](crate::hooks::use_navigate) that do not set their own
+    /// [`debounce`](crate::NavigateOptions::debounce).
+    ///
+    /// Defaults to `None`.
+    pub navigation_debounce: Option<Duration>,
+    /// If `true`, the end of each navigation (updating the address bar and scrolling) is wrapped
+    /// in a [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API),
+    /// in browsers that support them. See
+    /// [`use_view_transition_state`](crate::hooks::use_view_transition_state).
+    ///
+    /// Defaults to `false`.
+    pub view_transitions: bool,
+}
+
+impl Default for BrowserUrlOptions {
+    fn default() -> Self {
+        Self {
+            dispatch_navigation_event: false,
+            history_limit: 100,
+            enforce_noopener: true,
+            navigation_debounce: None,
+            view_transitions: false,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct BrowserUrl {
     url: ArcRwSignal<Url>,
+    pub(crate) hash: ArcRwSignal<String>,
+    pub(crate) state: ArcRwSignal<State>,
+    options: BrowserUrlOptions,
     pub(crate) pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
-    pub(crate) path_stack: ArcStoredValue<Vec<Url>>,
+    pub(crate) path_stack: ArcRwSignal<Vec<Url>>,
     pub(crate) is_back: ArcRwSignal<bool>,
+    pub(crate) is_forward: ArcRwSignal<bool>,
+    pub(crate) history_length: ArcRwSignal<u32>,
+    pub(crate) navigation_pending: ArcRwSignal<bool>,
+    pub(crate) direction: ArcRwSignal<NavigationDirection>,
+    pub(crate) prefetch: ArcRwSignal<Option<Url>>,
+    pub(crate) view_transition_state: ArcRwSignal<ViewTransitionState>,
+    pub(crate) guards: NavigationGuards,
+    pub(crate) sticky_query: StickyQuery,
+    pub(crate) history_error: ArcRwSignal<Option<RouterError>>,
+    scroll_config: ScrollConfig,
+    override_scroll: Option<OverrideScroll>,
+    scroll_restoration: ScrollRestoration,
+    /// The scroll position saved in the history entry most recently returned to with the back or
+    /// forward button, to be restored once its routes have rendered.
+    pending_scroll: Arc<Mutex<Option<(f64, f64)>>>,
+    listeners: Arc<EventListeners>,
+    pub(crate) debouncer: Debouncer,
+    strict: bool,
 }
 
+/// The event listeners added to `window` by [`BrowserUrl::init`](LocationProvider::init), by event
+/// name.
+type EventListeners = Mutex<Vec<(&'static str, SendWrapper<JsValue>)>>;
+
 impl fmt::Debug for BrowserUrl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("BrowserUrl").finish_non_exhaustive()
@@ -34,42 +136,457 @@ impl fmt::Debug for BrowserUrl {
 }
 
 impl BrowserUrl {
-    fn scroll_to_el(loc_scroll: bool) {
-        if let Ok(hash) = window().location().hash() {
-            if !hash.is_empty() {
-                let hash = js_sys::decode_uri(&hash[1..])
+    /// Creates a new location provider with the given options.
+    pub fn new_with_options(
+        options: BrowserUrlOptions,
+    ) -> Result<Self, JsValue> {
+        let current = Self::current()?;
+        let hash = ArcRwSignal::new(current.hash().to_string());
+        let url = ArcRwSignal::new(current);
+        let path_stack = ArcRwSignal::new(
+            Self::current().map(|n| vec![n]).unwrap_or_default(),
+        );
+        let state = window()
+            .history()
+            .and_then(|history| history.state())
+            .map(Self::state_from_js)
+            .unwrap_or_default();
+        Ok(Self {
+            url,
+            hash,
+            state: ArcRwSignal::new(state),
+            options,
+            pending_navigation: Default::default(),
+            path_stack,
+            is_back: Default::default(),
+            is_forward: Default::default(),
+            history_length: ArcRwSignal::new(Self::current_history_length()),
+            navigation_pending: Default::default(),
+            direction: Default::default(),
+            prefetch: Default::default(),
+            view_transition_state: Default::default(),
+            guards: Default::default(),
+            sticky_query: Default::default(),
+            history_error: Default::default(),
+            scroll_config: Default::default(),
+            override_scroll: use_context::<OverrideScroll>(),
+            scroll_restoration: Default::default(),
+            pending_scroll: Default::default(),
+            listeners: Default::default(),
+            debouncer: Default::default(),
+            strict: false,
+        })
+    }
+
+    /// Reads the base path from the page's `<base href>` element, if there is one.
+    ///
+    /// The path is resolved against the current origin and any trailing slash is removed, so
+    /// `<base href="/app/">` gives `/app`. Returns `None` if there is no `<base>` element, or if
+    /// it points to the root of the site.
+    pub fn base_from_document() -> Option<Cow<'static, str>> {
+        let href = document()
+            .query_selector("base")
+            .ok()
+            .flatten()?
+            .get_attribute("href")?;
+        let origin = window().location().origin().ok()?;
+        let url = Self::parse_with_base(&href, &origin).ok()?;
+        match url.path().trim_end_matches('/') {
+            "" => None,
+            path => Some(Cow::Owned(path.to_string())),
+        }
+    }
+
+    /// Sets how the page is scrolled at the end of each navigation.
+    pub fn with_scroll_config(mut self, scroll_config: ScrollConfig) -> Self {
+        self.scroll_config = scroll_config;
+        self
+    }
+
+    /// Sets whether the scroll position of each history entry is restored when the user goes back
+    /// or forward to it.
+    pub fn with_scroll_restoration(
+        mut self,
+        scroll_restoration: ScrollRestoration,
+    ) -> Self {
+        self.scroll_restoration = scroll_restoration;
+        self
+    }
+
+    /// Clears the history stack and any pending navigation, starting over from the current URL.
+    pub(crate) fn reset(&self) {
+        self.path_stack
+            .set(Self::current().map(|n| vec![n]).unwrap_or_default());
+        self.is_back.set(false);
+        self.is_forward.set(false);
+        self.pending_navigation.lock().or_poisoned().take();
+        self.navigation_pending.set(false);
+        self.direction.set(NavigationDirection::Unknown);
+    }
+
+    /// Navigates to the given path immediately, without waiting for any route data to load.
+    ///
+    /// This pushes the new URL onto the browser's history stack and updates the reactive URL
+    /// signal synchronously. Unlike navigations triggered by [`use_navigate`] or by clicking a
+    /// link, it does not wait for the new routes to load before updating the address bar. Any
+    /// navigation that is still waiting for its routes to load is canceled. This is intended for
+    /// simple navigations to routes that do not load async data.
+    ///
+    /// Sticky query parameters, interceptors, and synchronous navigation guards are applied as
+    /// for any other navigation, but **async guards are skipped**, because they cannot be
+    /// awaited here. If an interceptor or guard redirects the navigation, the redirect target is
+    /// not checked again. The history entry's state is cleared.
+    ///
+    /// Returns [`RouterError::Blocked`] if an interceptor or guard cancels the navigation.
+    pub fn navigate_sync(&self, path: &str) -> Result<(), RouterError> {
+        let parse = |path: &str| {
+            Self::parse(path).map_err(|e| RouterError::Parse(format!("{e:?}")))
+        };
+        let from = self.url.get_untracked();
+        let mut url = parse(path)?;
+        self.sticky_query.apply(&from, &mut url);
+
+        let decision = match NavigationDecision::from(
+            self.guards
+                .intercept(&url.to_full_path(), &NavigateOptions::default()),
+        ) {
+            NavigationDecision::Allow => self.guards.check(&NavigationTarget {
+                from,
+                to: url.clone(),
+            }),
+            decision => decision,
+        };
+        let url = match decision {
+            NavigationDecision::Allow => url,
+            NavigationDecision::Deny => {
+                return Err(RouterError::Blocked(url.to_full_path()))
+            }
+            NavigationDecision::Redirect(path) => {
+                let from = self.url.get_untracked();
+                let mut url = parse(&path)?;
+                self.sticky_query.apply(&from, &mut url);
+                url
+            }
+        };
+
+        // cancel, rather than complete, any navigation that is still waiting for its routes, so
+        // that it does not push its own URL after this one
+        self.pending_navigation.lock().or_poisoned().take();
+        self.pending_scroll.lock().or_poisoned().take();
+
+        let value = url.to_full_path();
+        window()
+            .history()
+            .and_then(|history| {
+                history.push_state_with_url(
+                    &JsValue::UNDEFINED,
+                    "",
+                    Some(&value),
+                )
+            })
+            .map_err(|e| RouterError::History(format!("{e:?}")))?;
+
+        self.push_to_path_stack(url.clone());
+        self.is_back.set(false);
+        self.is_forward.set(false);
+        self.direction.set(NavigationDirection::Forward);
+        Self::update_history_length(&self.history_length);
+        Self::set_hash(&self.hash, url.hash());
+        self.state.set(State::default());
+        self.url.set(url);
+        if *self.navigation_pending.read_untracked() {
+            self.navigation_pending.set(false);
+        }
+        Ok(())
+    }
+
+    /// Adds a URL to the in-app history stack, discarding the oldest entries if the stack has
+    /// grown past the configured limit.
+    fn push_to_path_stack(&self, url: Url) {
+        let limit = self.options.history_limit.max(1);
+        self.path_stack.update(|stack| {
+            stack.push(url);
+            if stack.len() > limit {
+                stack.drain(..stack.len() - limit);
+            }
+        });
+    }
+
+    fn dispatch_navigation_event(from: Option<&str>, to: &str) {
+        let detail = Object::new();
+        _ = Reflect::set(
+            &detail,
+            &JsValue::from_str("from"),
+            &from.map(JsValue::from_str).unwrap_or(JsValue::NULL),
+        );
+        _ = Reflect::set(
+            &detail,
+            &JsValue::from_str("to"),
+            &JsValue::from_str(to),
+        );
+        let init = CustomEventInit::new();
+        init.set_cancelable(true);
+        init.set_detail(&detail);
+        if let Ok(event) =
+            CustomEvent::new_with_event_init_dict("leptos:navigate", &init)
+        {
+            _ = window().dispatch_event(&event);
+        }
+    }
+
+    /// Keeps track of an event listener added to `window`, so that it can be removed by
+    /// [`dispose`](LocationProvider::dispose).
+    fn track_listener(&self, event: &'static str, closure: JsValue) {
+        self.listeners
+            .lock()
+            .or_poisoned()
+            .push((event, SendWrapper::new(closure)));
+    }
+
+    fn scroll_to_el(&self, loc: &LocationChange) {
+        // an explicit scroll target takes priority over the hash
+        let el = loc
+            .scroll_target
+            .as_deref()
+            .and_then(|id| document().get_element_by_id(id))
+            .or_else(|| {
+                window()
+                    .location()
+                    .hash()
                     .ok()
-                    .and_then(|decoded| decoded.as_string())
-                    .unwrap_or(hash);
-                let el = document().get_element_by_id(&hash);
-                if let Some(el) = el {
-                    el.scroll_into_view();
-                    return;
+                    .filter(|hash| !hash.is_empty())
+                    .and_then(|hash| {
+                        let hash = js_sys::decode_uri(&hash[1..])
+                            .ok()
+                            .and_then(|decoded| decoded.as_string())
+                            .unwrap_or(hash);
+                        document().get_element_by_id(&hash)
+                    })
+            });
+
+        // scroll to the target element, or to the top
+        if el.is_some() || loc.scroll {
+            match &self.override_scroll {
+                Some(OverrideScroll(override_scroll)) => {
+                    override_scroll(el.as_ref())
                 }
+                None => self.scroll_config.scroll_to(el.as_ref()),
             }
         }
+    }
+
+    /// Warns that a navigation started while another one was still pending, which cancels the
+    /// earlier navigation. This is usually caused by navigating twice in the same tick.
+    fn warn_overwritten_navigation(&self, to: &str) {
+        let message = format!(
+            "[leptos_router] started a navigation to {to:?} while another \
+             navigation was still pending; the earlier navigation was \
+             canceled"
+        );
+        #[cfg(feature = "tracing")]
+        tracing::warn!("{message}");
+        #[cfg(not(feature = "tracing"))]
+        web_sys::console::warn_1(&JsValue::from_str(&message));
+        debug_assert!(
+            !self.strict,
+            "overlapping navigations are not allowed in strict mode"
+        );
+    }
 
-        // scroll to top
-        if loc_scroll {
-            window().scroll_to_with_x_and_y(0.0, 0.0);
+    /// Reads `window.history.length`.
+    fn current_history_length() -> u32 {
+        window()
+            .history()
+            .and_then(|history| history.length())
+            .unwrap_or(1)
+    }
+
+    /// Updates the history length signal from `window.history.length`, if it has changed.
+    fn update_history_length(history_length: &ArcRwSignal<u32>) {
+        let length = Self::current_history_length();
+        if *history_length.read_untracked() != length {
+            history_length.set(length);
+        }
+    }
+
+    /// Converts the state of a history entry, which is `null` if it has none, into a [`State`].
+    fn state_from_js(state: JsValue) -> State {
+        if state.is_null() || state.is_undefined() {
+            return State::new(None);
+        }
+
+        // hide the saved scroll position from the app
+        let key = JsValue::from_str(SCROLL_POSITION_KEY);
+        if state.is_object() && Reflect::has(&state, &key).unwrap_or(false) {
+            let state = Object::assign(&Object::new(), state.unchecked_ref());
+            _ = Reflect::delete_property(&state, &key);
+            return State::new(
+                (Object::keys(&state).length() > 0).then(|| state.into()),
+            );
+        }
+        State::new(Some(state))
+    }
+
+    /// Saves the current scroll position in the current history entry's state, so that it can be
+    /// restored when the user comes back to this entry. Call this before navigating away.
+    ///
+    /// This also discards any position that was waiting to be restored, because the navigation
+    /// that restored it has been superseded.
+    pub(crate) fn save_scroll_position(&self) {
+        if !self.scroll_restoration.auto {
+            return;
+        }
+        self.pending_scroll.lock().or_poisoned().take();
+
+        let window = window();
+        let (Ok(history), Ok(x), Ok(y)) =
+            (window.history(), window.scroll_x(), window.scroll_y())
+        else {
+            return;
+        };
+        let state = history.state().unwrap_or(JsValue::UNDEFINED);
+        let new_state = if state.is_null() || state.is_undefined() {
+            Object::new()
+        } else if state.is_object() && !Array::is_array(&state) {
+            // copy the app's state, rather than mutating an object it may still hold
+            Object::assign(&Object::new(), state.unchecked_ref())
+        } else {
+            // there is nowhere to put the position in any other kind of state
+            return;
+        };
+        _ = Reflect::set(
+            &new_state,
+            &JsValue::from_str(SCROLL_POSITION_KEY),
+            &Array::of2(&JsValue::from(x), &JsValue::from(y)),
+        );
+        _ = history.replace_state(&new_state, "");
+    }
+
+    /// Reads the scroll position saved by [`save_scroll_position`](Self::save_scroll_position)
+    /// from a history entry's state.
+    fn saved_scroll_position(state: &JsValue) -> Option<(f64, f64)> {
+        if !state.is_object() {
+            return None;
+        }
+        let position =
+            Reflect::get(state, &JsValue::from_str(SCROLL_POSITION_KEY))
+                .ok()?
+                .dyn_into::<Array>()
+                .ok()?;
+        Some((position.get(0).as_f64()?, position.get(1).as_f64()?))
+    }
+
+    /// Updates the state signal, if the state has changed.
+    fn set_state(state: &ArcRwSignal<State>, new_state: State) {
+        if *state.read_untracked() != new_state {
+            state.set(new_state);
+        }
+    }
+
+    /// Updates the hash signal, if the hash has changed.
+    fn set_hash(hash: &ArcRwSignal<String>, new_hash: &str) {
+        if *hash.read_untracked() != new_hash {
+            hash.set(new_hash.to_string());
+        }
+    }
+
+    /// Adds the new location to the browser's session history, replacing the current entry if
+    /// `loc.replace` is set.
+    fn update_history(loc: &LocationChange) -> Result<(), JsValue> {
+        let history = window().history()?;
+        let state = loc.state.to_js_value();
+        if loc.replace {
+            history.replace_state_with_url(&state, "", Some(&loc.value))
+        } else {
+            history.push_state_with_url(&state, "", Some(&loc.value))
+        }
+    }
+
+    /// Records an error thrown by the History API, so that it can be read with
+    /// [`use_router_error`](crate::hooks::use_router_error).
+    ///
+    /// If the browser refused the update for security reasons (for example, because the new URL
+    /// is not same-origin with the document), this falls back to a full-page navigation with
+    /// `location.assign()`.
+    fn handle_history_error(&self, loc: &LocationChange, e: JsValue) {
+        let exception = e.dyn_ref::<web_sys::DomException>();
+        let is_security_error =
+            exception.is_some_and(|e| e.name() == "SecurityError");
+        let description = match exception {
+            Some(e) => format!("{}: {}", e.name(), e.message()),
+            None => format!("{e:?}"),
+        };
+        let message = format!(
+            "[leptos_router] could not update browser history for {:?}: \
+             {description}",
+            loc.value
+        );
+        #[cfg(feature = "tracing")]
+        tracing::error!("{message}");
+        #[cfg(not(feature = "tracing"))]
+        web_sys::console::error_1(&JsValue::from_str(&message));
+
+        self.history_error
+            .set(Some(RouterError::History(description)));
+
+        if is_security_error {
+            if let Err(e) = window().location().assign(&loc.value) {
+                leptos::logging::error!("Failed to navigate: {e:#?}");
+            }
         }
     }
+
+    /// Updates the address bar and history stack at the end of a navigation, and scrolls.
+    fn commit_navigation(&self, loc: &LocationChange) {
+        let from = self
+            .path_stack
+            .read_untracked()
+            .last()
+            .map(Url::to_full_path);
+
+        if let Err(e) = Self::update_history(loc) {
+            self.handle_history_error(loc, e);
+            return;
+        }
+        if self.history_error.with_untracked(Option::is_some) {
+            self.history_error.set(None);
+        }
+
+        // add this URL to the "path stack" for detecting back navigations, and
+        // unset "navigating back" state
+        if let Ok(url) = Self::current() {
+            self.push_to_path_stack(url);
+            self.is_back.set(false);
+            self.is_forward.set(false);
+        }
+        Self::update_history_length(&self.history_length);
+        let direction = NavigationDirection::from_replace(loc.replace);
+        if *self.direction.read_untracked() != direction {
+            self.direction.set(direction);
+        }
+
+        if self.options.dispatch_navigation_event {
+            Self::dispatch_navigation_event(from.as_deref(), &loc.value);
+        }
+
+        // scroll to el
+        self.scroll_to_el(loc);
+    }
 }
 
 impl LocationProvider for BrowserUrl {
     type Error = JsValue;
 
+    /// Creates a location provider using the options from the [`RouterConfig`] provided via
+    /// context, if any, or the default options otherwise.
     fn new() -> Result<Self, JsValue> {
-        let url = ArcRwSignal::new(Self::current()?);
-        let path_stack = ArcStoredValue::new(
-            Self::current().map(|n| vec![n]).unwrap_or_default(),
-        );
-        Ok(Self {
-            url,
-            pending_navigation: Default::default(),
-            path_stack,
-            is_back: Default::default(),
-        })
+        let config = use_context::<RouterConfig>().unwrap_or_default();
+        let mut this =
+            Self::new_with_options(config.get_location_options().clone())?
+                .with_scroll_config(config.get_scroll_config())
+                .with_scroll_restoration(config.get_scroll_restoration());
+        this.strict = config.is_strict();
+        Ok(this)
     }
 
     fn as_url(&self) -> &ArcRwSignal<Url> {
@@ -78,6 +595,9 @@ impl LocationProvider for BrowserUrl {
 
     fn current() -> Result<Url, Self::Error> {
         let location = window().location();
+        // `window.location` does not expose credentials, so read them from the full URL
+        let (username, password) =
+            credentials(&web_sys::Url::new(&location.href()?)?);
         Ok(Url {
             origin: location.origin()?,
             path: location.pathname()?,
@@ -90,16 +610,19 @@ impl LocationProvider for BrowserUrl {
                 &UrlSearchParams::new_with_str(&location.search()?)?,
             )?,
             hash: location.hash()?,
+            username,
+            password,
         })
     }
 
-    fn parse(url: &str) -> Result<Url, Self::Error> {
+    fn parse(url: impl AsRef<str>) -> Result<Url, Self::Error> {
         let base = window().location().origin()?;
-        Self::parse_with_base(url, &base)
+        Self::parse_with_base(url.as_ref(), &base)
     }
 
     fn parse_with_base(url: &str, base: &str) -> Result<Url, Self::Error> {
         let location = web_sys::Url::new_with_base(url, base)?;
+        let (username, password) = credentials(&location);
         Ok(Url {
             origin: location.origin(),
             path: location.pathname(),
@@ -112,35 +635,97 @@ impl LocationProvider for BrowserUrl {
                 &location.search_params(),
             )?,
             hash: location.hash(),
+            username,
+            password,
         })
     }
 
     fn init(&self, base: Option<Cow<'static, str>>) {
         let window = window();
+
+        // the router restores scroll positions itself, once the routes have rendered
+        if self.scroll_restoration.auto {
+            if let Ok(history) = window.history() {
+                _ = history
+                    .set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+            }
+        }
+
+        // remove the event listeners when the router is unmounted, so that they do not pile up
+        // if it is mounted again
+        on_cleanup({
+            let this = self.clone();
+            move || this.dispose()
+        });
+
         let navigate = {
             let url = self.url.clone();
             let pending = Arc::clone(&self.pending_navigation);
             let this = self.clone();
-            move |new_url: Url, loc| {
-                let same_path = {
-                    let curr = url.read_untracked();
-                    curr.origin() == new_url.origin()
-                        && curr.path() == new_url.path()
-                };
-
-                url.set(new_url.clone());
-                if same_path {
-                    this.complete_navigation(&loc);
-                }
-                let pending = Arc::clone(&pending);
-                let (tx, rx) = oneshot::channel::<()>();
-                if !same_path {
-                    *pending.lock().or_poisoned() = Some(tx);
+            move |mut new_url: Url, mut loc: LocationChange| {
+                let from = url.get_untracked();
+                // carry any sticky query parameters over into the new URL
+                if this.sticky_query.apply(&from, &mut new_url) {
+                    loc.value = new_url.to_full_path();
                 }
+
+                // give any interceptors, and then any navigation guards, a chance to cancel or
+                // redirect the navigation
+                let target = NavigationTarget { from, to: new_url };
+                let intercepted = this.guards.intercept(
+                    &loc.value,
+                    &NavigateOptions::from_location_change(&loc),
+                );
+                let decision = match NavigationDecision::from(intercepted) {
+                    NavigationDecision::Allow => {
+                        Either::Left(this.guards.check_async(target.clone()))
+                    }
+                    decision => Either::Right(future::ready(decision)),
+                };
                 let url = url.clone();
+                let pending = Arc::clone(&pending);
                 let this = this.clone();
                 async move {
+                    let (new_url, loc) = match decision.await {
+                        NavigationDecision::Allow => (target.to, loc),
+                        NavigationDecision::Deny => return,
+                        NavigationDecision::Redirect(path) => {
+                            let Ok(new_url) = Self::parse(&path) else {
+                                return;
+                            };
+                            let loc = LocationChange {
+                                scroll_target: loc.scroll_target,
+                                ..LocationChange::from_url_with_state(
+                                    &new_url,
+                                    loc.replace,
+                                    loc.scroll,
+                                    loc.state,
+                                )
+                            };
+                            (new_url, loc)
+                        }
+                    };
+
+                    let same_path =
+                        url.read_untracked().is_same_document(&new_url);
+
+                    this.direction
+                        .set(NavigationDirection::from_replace(loc.replace));
+                    Self::set_hash(&this.hash, new_url.hash());
+                    if !loc.replace {
+                        this.save_scroll_position();
+                    }
+                    url.set(new_url.clone());
+                    if same_path {
+                        this.complete_navigation(&loc);
+                    }
                     if !same_path {
+                        let (tx, rx) = oneshot::channel::<()>();
+                        let previous = pending.lock().or_poisoned().replace(tx);
+                        if previous.is_some() {
+                            this.warn_overwritten_navigation(&loc.value);
+                        }
+                        this.navigation_pending.set(true);
                         // if it has been canceled, ignore
                         // otherwise, complete navigation -- i.e., set URL in address bar
                         if rx.await.is_ok() {
@@ -157,8 +742,30 @@ impl LocationProvider for BrowserUrl {
             }
         };
 
-        let handle_anchor_click =
-            handle_anchor_click(base, Self::parse_with_base, navigate);
+        // if the router debounces navigations, wait to see whether another one starts first
+        let navigate = {
+            let debounce = self.options.navigation_debounce;
+            let debouncer = self.debouncer.clone();
+            move |new_url: Url, loc: LocationChange| {
+                let navigate = navigate.clone();
+                let waiting = debounce.map(|delay| debouncer.wait(delay));
+                async move {
+                    if let Some(waiting) = waiting {
+                        if waiting.await.is_err() {
+                            return;
+                        }
+                    }
+                    navigate(new_url, loc).await
+                }
+            }
+        };
+
+        let handle_anchor_click = handle_anchor_click(
+            base.clone(),
+            Self::parse_with_base,
+            self.options.enforce_noopener,
+            navigate.clone(),
+        );
         let closure = Closure::wrap(Box::new(move |ev: Event| {
             if let Err(e) = handle_anchor_click(ev) {
                 #[cfg(feature = "tracing")]
@@ -177,21 +784,109 @@ impl LocationProvider for BrowserUrl {
                 "couldn't add `click` listener to `window` to handle `<a>` \
                  clicks",
             );
+        self.track_listener("click", closure);
+
+        // clicks with any button other than the main one (like a middle click to open a link in
+        // a new tab) are always left to the browser, so `auxclick` is explicitly a no-op
+        let closure =
+            Closure::wrap(Box::new(|_: Event| {}) as Box<dyn FnMut(Event)>)
+                .into_js_value();
+        window
+            .add_event_listener_with_callback(
+                "auxclick",
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect("couldn't add `auxclick` listener to `window`");
+        self.track_listener("auxclick", closure);
+
+        // turn same-origin `<form method="get">` submissions into client-side navigations
+        let handle_form_submit =
+            handle_form_submit(base, Self::parse_with_base, navigate);
+        let closure = Closure::wrap(Box::new(move |ev: Event| {
+            if let Err(e) = handle_form_submit(ev) {
+                #[cfg(feature = "tracing")]
+                tracing::error!("{e:?}");
+                #[cfg(not(feature = "tracing"))]
+                web_sys::console::error_1(&e);
+            }
+        }) as Box<dyn FnMut(Event)>)
+        .into_js_value();
+        window
+            .add_event_listener_with_callback(
+                "submit",
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect(
+                "couldn't add `submit` listener to `window` to handle \
+                 `<form>` submissions",
+            );
+        self.track_listener("submit", closure);
+
+        // prefetch routes for links marked with data-prefetch="intent" when hovered
+        let handle_anchor_hover = handle_anchor_hover(Self::parse_with_base, {
+            let prefetch = self.prefetch.clone();
+            move |url| {
+                if prefetch.read_untracked().as_ref() != Some(&url) {
+                    prefetch.set(Some(url));
+                }
+            }
+        });
+        let closure = Closure::wrap(Box::new(move |ev: Event| {
+            if let Err(e) = handle_anchor_hover(ev) {
+                #[cfg(feature = "tracing")]
+                tracing::error!("{e:?}");
+                #[cfg(not(feature = "tracing"))]
+                web_sys::console::error_1(&e);
+            }
+        }) as Box<dyn FnMut(Event)>)
+        .into_js_value();
+        window
+            .add_event_listener_with_callback(
+                "mouseover",
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect(
+                "couldn't add `mouseover` listener to `window` to handle \
+                 `<a>` prefetching",
+            );
+        self.track_listener("mouseover", closure);
 
         // handle popstate event (forward/back navigation)
         let cb = {
             let url = self.url.clone();
+            let hash = self.hash.clone();
+            let history_length = self.history_length.clone();
+            let state = self.state.clone();
             let path_stack = self.path_stack.clone();
             let is_back = self.is_back.clone();
-            move || match Self::current() {
+            let is_forward = self.is_forward.clone();
+            let direction = self.direction.clone();
+            let scroll_restoration = self.scroll_restoration;
+            let pending_scroll = Arc::clone(&self.pending_scroll);
+            move |ev: Event| match Self::current() {
                 Ok(new_url) => {
-                    let stack = path_stack.read_value();
+                    let ev = ev.unchecked_into::<PopStateEvent>();
+                    if scroll_restoration.auto {
+                        *pending_scroll.lock().or_poisoned() =
+                            Self::saved_scroll_position(&ev.state());
+                    }
+                    let stack = path_stack.read_untracked();
                     let is_navigating_back = stack.len() == 1
                         || (stack.len() >= 2
                             && stack.get(stack.len() - 2) == Some(&new_url));
 
+                    // a popstate that is not a back navigation can only come from going forward
                     is_back.set(is_navigating_back);
+                    is_forward.set(!is_navigating_back);
+                    direction.set(if is_navigating_back {
+                        NavigationDirection::Back
+                    } else {
+                        NavigationDirection::Forward
+                    });
 
+                    Self::update_history_length(&history_length);
+                    Self::set_state(&state, Self::state_from_js(ev.state()));
+                    Self::set_hash(&hash, new_url.hash());
                     url.set(new_url);
                 }
                 Err(e) => {
@@ -203,49 +898,93 @@ impl LocationProvider for BrowserUrl {
             }
         };
         let closure =
-            Closure::wrap(Box::new(cb) as Box<dyn Fn()>).into_js_value();
+            Closure::wrap(Box::new(cb) as Box<dyn Fn(Event)>).into_js_value();
         window
             .add_event_listener_with_callback(
                 "popstate",
                 closure.as_ref().unchecked_ref(),
             )
             .expect("couldn't add `popstate` listener to `window`");
+        self.track_listener("popstate", closure);
+
+        // handle hashchange event, so that `Location::hash` updates without waiting for the
+        // rest of the URL
+        let cb = {
+            let hash = self.hash.clone();
+            let state = self.state.clone();
+            let window = window.clone();
+            move || match window.location().hash() {
+                Ok(new_hash) => {
+                    if let Ok(new_state) =
+                        window.history().and_then(|history| history.state())
+                    {
+                        Self::set_state(&state, Self::state_from_js(new_state));
+                    }
+                    Self::set_hash(&hash, &new_hash);
+                }
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("{e:?}");
+                    #[cfg(not(feature = "tracing"))]
+                    web_sys::console::error_1(&e);
+                }
+            }
+        };
+        let closure =
+            Closure::wrap(Box::new(cb) as Box<dyn Fn()>).into_js_value();
+        window
+            .add_event_listener_with_callback(
+                "hashchange",
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect("couldn't add `hashchange` listener to `window`");
+        self.track_listener("hashchange", closure);
+    }
+
+    fn dispose(&self) {
+        let listeners = mem::take(&mut *self.listeners.lock().or_poisoned());
+        let window = window();
+        if self.scroll_restoration.auto {
+            if let Ok(history) = window.history() {
+                _ = history
+                    .set_scroll_restoration(web_sys::ScrollRestoration::Auto);
+            }
+        }
+        for (event, closure) in listeners {
+            _ = window.remove_event_listener_with_callback(
+                event,
+                closure.unchecked_ref(),
+            );
+        }
     }
 
     fn ready_to_complete(&self) {
         if let Some(tx) = self.pending_navigation.lock().or_poisoned().take() {
             _ = tx.send(());
         }
+        if let Some((x, y)) = self.pending_scroll.lock().or_poisoned().take() {
+            // wait for the restored routes to be painted, so that the page is tall enough
+            request_animation_frame(move || {
+                window().scroll_to_with_x_and_y(x, y)
+            });
+        }
+        if *self.navigation_pending.read_untracked() {
+            self.navigation_pending.set(false);
+        }
     }
 
     fn complete_navigation(&self, loc: &LocationChange) {
-        let history = window().history().unwrap();
-
-        if loc.replace {
-            history
-                .replace_state_with_url(
-                    &loc.state.to_js_value(),
-                    "",
-                    Some(&loc.value),
-                )
-                .unwrap();
+        if self.options.view_transitions {
+            let this = self.clone();
+            let loc = loc.clone();
+            run_view_transition(
+                Some(self.view_transition_state.clone()),
+                move || this.commit_navigation(&loc),
+                || {},
+            );
         } else {
-            // push the "forward direction" marker
-            let state = &loc.state.to_js_value();
-            history
-                .push_state_with_url(state, "", Some(&loc.value))
-                .unwrap();
+            self.commit_navigation(loc);
         }
-
-        // add this URL to the "path stack" for detecting back navigations, and
-        // unset "navigating back" state
-        if let Ok(url) = Self::current() {
-            self.path_stack.write_value().push(url);
-            self.is_back.set(false);
-        }
-
-        // scroll to el
-        Self::scroll_to_el(loc.scroll);
     }
 
     fn redirect(loc: &str) {
@@ -269,6 +1008,24 @@ impl LocationProvider for BrowserUrl {
     fn is_back(&self) -> ReadSignal<bool> {
         self.is_back.read_only().into()
     }
+
+    fn is_forward(&self) -> ReadSignal<bool> {
+        self.is_forward.read_only().into()
+    }
+
+    fn direction(&self) -> ReadSignal<NavigationDirection> {
+        self.direction.read_only().into()
+    }
+
+    fn history_length(&self) -> Signal<u32> {
+        self.history_length.read_only().into()
+    }
+
+    fn view_transition_state(
+        &self,
+    ) -> Option<ArcRwSignal<ViewTransitionState>> {
+        Some(self.view_transition_state.clone())
+    }
 }
 
 fn search_params_from_web_url(
@@ -289,6 +1046,13 @@ fn search_params_from_web_url(
         .collect()
 }
 
+/// Reads the username and password from a URL's authority. The browser returns an empty string
+/// for a missing part.
+fn credentials(url: &web_sys::Url) -> (Option<String>, Option<String>) {
+    let non_empty = |s: String| (!s.is_empty()).then_some(s);
+    (non_empty(url.username()), non_empty(url.password()))
+}
+
 /// Resolves a redirect location to an (absolute) URL.
 pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
     let origin = match window().location().origin() {
@@ -0,0 +1,217 @@
+use super::{LocationChange, LocationProvider, NavigationDirection, Url, BASE};
+use crate::hooks::use_navigate;
+use core::fmt;
+use futures::channel::oneshot;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    signal::{ArcRwSignal, ReadSignal},
+    traits::{ReadUntracked, Set},
+};
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+
+/// A [`LocationProvider`] that keeps its history stack in memory, rather than in the browser.
+///
+/// Because it does not touch `window`, `document`, or `history`, this can be used to exercise
+/// routing logic in unit or integration tests that do not run in a browser.
+#[derive(Clone)]
+pub struct MemoryRouter {
+    url: ArcRwSignal<Url>,
+    stack: Arc<Mutex<Vec<Url>>>,
+    pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+    is_back: ArcRwSignal<bool>,
+    direction: ArcRwSignal<NavigationDirection>,
+}
+
+impl fmt::Debug for MemoryRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryRouter")
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemoryRouter {
+    /// Creates a new in-memory location provider, starting at the given URL.
+    pub fn new_with_url(initial: &str) -> Result<Self, url::ParseError> {
+        let url = Self::parse(initial)?;
+        Ok(Self {
+            url: ArcRwSignal::new(url.clone()),
+            stack: Arc::new(Mutex::new(vec![url])),
+            pending_navigation: Default::default(),
+            is_back: Default::default(),
+            direction: Default::default(),
+        })
+    }
+
+    /// Returns the URL at the top of the history stack.
+    pub fn current_url(&self) -> Url {
+        self.stack
+            .lock()
+            .or_poisoned()
+            .last()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns every URL in the history stack, from oldest to newest.
+    pub fn history(&self) -> Vec<Url> {
+        self.stack.lock().or_poisoned().clone()
+    }
+
+    /// Simulates a "back" navigation, removing the top entry from the history stack.
+    ///
+    /// Returns `false` without doing anything if there is no previous entry to go back to.
+    pub fn back(&self) -> bool {
+        let previous = {
+            let mut stack = self.stack.lock().or_poisoned();
+            if stack.len() < 2 {
+                return false;
+            }
+            stack.pop();
+            stack.last().cloned().unwrap_or_default()
+        };
+        self.is_back.set(true);
+        self.direction.set(NavigationDirection::Back);
+        self.url.set(previous);
+        true
+    }
+}
+
+impl LocationProvider for MemoryRouter {
+    type Error = url::ParseError;
+
+    fn new() -> Result<Self, Self::Error> {
+        Self::new_with_url("/")
+    }
+
+    fn as_url(&self) -> &ArcRwSignal<Url> {
+        &self.url
+    }
+
+    /// There is no global location for an in-memory router, so this always returns the root
+    /// URL. Use [`MemoryRouter::current_url`] to get the current URL of a given router.
+    fn current() -> Result<Url, Self::Error> {
+        Self::parse("/")
+    }
+
+    fn init(&self, _base: Option<Cow<'static, str>>) {}
+
+    fn ready_to_complete(&self) {
+        if let Some(tx) = self.pending_navigation.lock().or_poisoned().take() {
+            _ = tx.send(());
+        }
+    }
+
+    fn complete_navigation(&self, loc: &LocationChange) {
+        let Ok(url) = Self::parse(&loc.value) else {
+            leptos::logging::error!("Error parsing URL: {}", loc.value);
+            return;
+        };
+
+        {
+            let mut stack = self.stack.lock().or_poisoned();
+            if loc.replace {
+                stack.pop();
+            }
+            stack.push(url.clone());
+        }
+        self.is_back.set(false);
+        self.direction
+            .set(NavigationDirection::from_replace(loc.replace));
+        if *self.url.read_untracked() != url {
+            self.url.set(url);
+        }
+    }
+
+    fn parse_with_base(url: &str, base: &str) -> Result<Url, Self::Error> {
+        let base = url::Url::parse(base)?;
+        let url = url::Url::options().base_url(Some(&base)).parse(url)?;
+        Ok(Url::from_parsed(&url))
+    }
+
+    fn parse(url: impl AsRef<str>) -> Result<Url, Self::Error> {
+        Self::parse_with_base(url.as_ref(), BASE)
+    }
+
+    fn redirect(loc: &str) {
+        let navigate = use_navigate();
+        navigate(loc, Default::default());
+    }
+
+    fn is_back(&self) -> ReadSignal<bool> {
+        self.is_back.read_only().into()
+    }
+
+    fn direction(&self) -> ReadSignal<NavigationDirection> {
+        self.direction.read_only().into()
+    }
+}
+
+// parsing URLs with query strings uses `Url::unescape`, which calls into JS outside `ssr`
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::MemoryRouter;
+    use crate::location::{
+        LocationChange, LocationProvider, NavigationDirection,
+    };
+    use reactive_graph::traits::GetUntracked;
+
+    fn navigate(router: &MemoryRouter, value: &str, replace: bool) {
+        router.complete_navigation(
+            &LocationChange::to(value).replace(replace).build(),
+        );
+    }
+
+    #[test]
+    fn starts_at_initial_url() {
+        let router = MemoryRouter::new_with_url("/foo?bar=baz#qux").unwrap();
+        let url = router.current_url();
+        assert_eq!(url.path(), "/foo");
+        assert_eq!(url.search(), "bar=baz");
+        assert_eq!(url.hash(), "#qux");
+        assert_eq!(router.history().len(), 1);
+    }
+
+    #[test]
+    fn parses_owned_and_borrowed_strings() {
+        let path = format!("/users/{}", 42);
+        assert_eq!(
+            MemoryRouter::parse(&path).unwrap(),
+            MemoryRouter::parse(path).unwrap()
+        );
+    }
+
+    #[test]
+    fn push_and_replace() {
+        let router = MemoryRouter::new().unwrap();
+        navigate(&router, "/a", false);
+        navigate(&router, "/b", true);
+        let paths = router
+            .history()
+            .iter()
+            .map(|url| url.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["/", "/b"]);
+        assert_eq!(router.current_url().path(), "/b");
+    }
+
+    #[test]
+    fn back_pops_the_stack() {
+        let router = MemoryRouter::new().unwrap();
+        navigate(&router, "/a", false);
+        assert_eq!(
+            router.direction().get_untracked(),
+            NavigationDirection::Forward
+        );
+        assert!(router.back());
+        assert_eq!(
+            router.direction().get_untracked(),
+            NavigationDirection::Back
+        );
+        assert_eq!(router.current_url().path(), "/");
+        assert!(!router.back());
+    }
+}
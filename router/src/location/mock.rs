@@ -0,0 +1,208 @@
+use super::{
+    Location, LocationChange, LocationProvider, NavigationDirection, State,
+    Url, BASE,
+};
+use core::fmt;
+use futures::channel::oneshot;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    signal::{ArcRwSignal, ReadSignal},
+    traits::{ReadUntracked, Set},
+};
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+
+/// A test double for the [`LocationProvider`] trait, which lets tests drive navigation events by
+/// hand.
+///
+/// Unlike [`MemoryRouter`](super::MemoryRouter), which simulates a history stack, this simply
+/// records the navigations it is asked to complete, and moves to whatever URL the test tells it
+/// to with [`mock_navigate`](Self::mock_navigate) or [`mock_popstate`](Self::mock_popstate).
+/// Its [`location`](Self::location) can be used to test code that reads a [`Location`] without
+/// rendering a `<Router/>`.
+#[derive(Clone)]
+pub struct MockLocationProvider {
+    url: ArcRwSignal<Url>,
+    state: ArcRwSignal<State>,
+    navigations: Arc<Mutex<Vec<LocationChange>>>,
+    pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+    is_back: ArcRwSignal<bool>,
+    direction: ArcRwSignal<NavigationDirection>,
+}
+
+impl fmt::Debug for MockLocationProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockLocationProvider")
+            .field("navigations", &self.navigations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockLocationProvider {
+    /// Creates a mock location provider, starting at the given URL.
+    pub fn new_with_url(initial: &str) -> Result<Self, url::ParseError> {
+        Ok(Self {
+            url: ArcRwSignal::new(Self::parse(initial)?),
+            state: ArcRwSignal::new(State::new(None)),
+            navigations: Default::default(),
+            pending_navigation: Default::default(),
+            is_back: Default::default(),
+            direction: Default::default(),
+        })
+    }
+
+    /// Simulates a forward navigation to the given URL, like a click on a link.
+    pub fn mock_navigate(&self, url: &str) -> Result<(), url::ParseError> {
+        let url = Self::parse(url)?;
+        self.is_back.set(false);
+        self.direction.set(NavigationDirection::Forward);
+        self.url.set(url);
+        Ok(())
+    }
+
+    /// Simulates a `popstate` event that moves back to the given URL, like a click on the
+    /// browser's back button.
+    pub fn mock_popstate(&self, url: &str) -> Result<(), url::ParseError> {
+        let url = Self::parse(url)?;
+        self.is_back.set(true);
+        self.direction.set(NavigationDirection::Back);
+        self.url.set(url);
+        Ok(())
+    }
+
+    /// Starts a navigation that will be resolved by the next call to
+    /// [`ready_to_complete`](LocationProvider::ready_to_complete).
+    ///
+    /// Any navigation that is already pending is dropped.
+    pub fn pending_navigation(&self) -> oneshot::Receiver<()> {
+        let (tx, rx) = oneshot::channel();
+        *self.pending_navigation.lock().or_poisoned() = Some(tx);
+        rx
+    }
+
+    /// Returns every navigation that has been passed to
+    /// [`complete_navigation`](LocationProvider::complete_navigation), oldest first.
+    pub fn navigations(&self) -> Vec<LocationChange> {
+        self.navigations.lock().or_poisoned().clone()
+    }
+
+    /// Returns a reactive [`Location`] that follows this provider's URL.
+    pub fn location(&self) -> Location {
+        Location::new(self.url.read_only(), self.state.read_only())
+    }
+}
+
+impl LocationProvider for MockLocationProvider {
+    type Error = url::ParseError;
+
+    fn new() -> Result<Self, Self::Error> {
+        Self::new_with_url("/")
+    }
+
+    fn as_url(&self) -> &ArcRwSignal<Url> {
+        &self.url
+    }
+
+    /// There is no global location for a mock provider, so this always returns the root URL.
+    fn current() -> Result<Url, Self::Error> {
+        Self::parse("/")
+    }
+
+    fn init(&self, _base: Option<Cow<'static, str>>) {}
+
+    fn ready_to_complete(&self) {
+        if let Some(tx) = self.pending_navigation.lock().or_poisoned().take() {
+            _ = tx.send(());
+        }
+    }
+
+    fn complete_navigation(&self, loc: &LocationChange) {
+        self.navigations.lock().or_poisoned().push(loc.clone());
+        let Ok(url) = Self::parse(&loc.value) else {
+            leptos::logging::error!("Error parsing URL: {}", loc.value);
+            return;
+        };
+        self.is_back.set(false);
+        self.direction
+            .set(NavigationDirection::from_replace(loc.replace));
+        self.state.set(loc.state.clone());
+        if *self.url.read_untracked() != url {
+            self.url.set(url);
+        }
+    }
+
+    fn parse_with_base(url: &str, base: &str) -> Result<Url, Self::Error> {
+        let base = url::Url::parse(base)?;
+        let url = url::Url::options().base_url(Some(&base)).parse(url)?;
+        Ok(Url::from_parsed(&url))
+    }
+
+    fn parse(url: impl AsRef<str>) -> Result<Url, Self::Error> {
+        Self::parse_with_base(url.as_ref(), BASE)
+    }
+
+    /// Redirects are not followed by a mock provider.
+    fn redirect(_loc: &str) {}
+
+    fn is_back(&self) -> ReadSignal<bool> {
+        self.is_back.read_only().into()
+    }
+
+    fn direction(&self) -> ReadSignal<NavigationDirection> {
+        self.direction.read_only().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockLocationProvider;
+    use crate::location::{
+        LocationChange, LocationProvider, NavigationDirection,
+    };
+    use futures::FutureExt;
+    use reactive_graph::traits::GetUntracked;
+
+    #[test]
+    fn mock_events_update_location() {
+        let provider = MockLocationProvider::new_with_url("/a").unwrap();
+        let location = provider.location();
+        assert_eq!(location.pathname.get_untracked(), "/a");
+
+        provider.mock_navigate("/b").unwrap();
+        assert_eq!(location.pathname.get_untracked(), "/b");
+        assert_eq!(
+            provider.direction().get_untracked(),
+            NavigationDirection::Forward
+        );
+
+        provider.mock_popstate("/a").unwrap();
+        assert_eq!(location.pathname.get_untracked(), "/a");
+        assert!(provider.is_back().get_untracked());
+    }
+
+    #[test]
+    fn ready_to_complete_resolves_pending_navigation() {
+        let provider = MockLocationProvider::new().unwrap();
+        let rx = provider.pending_navigation();
+        assert_eq!(rx.now_or_never(), None);
+
+        let rx = provider.pending_navigation();
+        provider.ready_to_complete();
+        assert_eq!(rx.now_or_never(), Some(Ok(())));
+    }
+
+    #[test]
+    fn records_completed_navigations() {
+        let provider = MockLocationProvider::new().unwrap();
+        let change = LocationChange::to("/next").replace(true).build();
+        provider.complete_navigation(&change);
+        assert_eq!(provider.navigations(), [change]);
+        assert_eq!(provider.as_url().get_untracked().path(), "/next");
+        assert_eq!(
+            provider.direction().get_untracked(),
+            NavigationDirection::Replace
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use super::Url;
+
+/// Multi-label public suffixes under which each label is registrable by a different party.
+///
+/// This is a deliberately small subset of the [Public Suffix List](https://publicsuffix.org/),
+/// covering the most common second-level country domains and hosting platforms.
+const MULTI_LABEL_SUFFIXES: &[&str] = &[
+    "ac.uk",
+    "co.uk",
+    "gov.uk",
+    "org.uk",
+    "com.au",
+    "net.au",
+    "org.au",
+    "co.jp",
+    "co.nz",
+    "co.in",
+    "com.br",
+    "com.cn",
+    "github.io",
+    "gitlab.io",
+    "herokuapp.com",
+    "netlify.app",
+    "pages.dev",
+    "vercel.app",
+    "workers.dev",
+];
+
+impl Url {
+    /// Whether this URL is on the same site as `other`: that is, whether both URLs have the
+    /// same scheme and the same registrable domain (the "eTLD+1", like `example.com` for
+    /// `app.example.com` or `example.co.uk` for `www.example.co.uk`).
+    ///
+    /// This is broader than comparing [`origin`](Url::origin)s, which also requires the
+    /// subdomain and port to match.
+    ///
+    /// The router itself still only handles links to the current origin: the History API cannot
+    /// push a URL from another origin, even one on the same site, so a link to another subdomain
+    /// always has to be followed by the browser. This can be used to decide how such a link
+    /// should be treated otherwise, like whether to mark it as external.
+    pub fn is_same_site(&self, other: &Url) -> bool {
+        match (
+            scheme_and_host(&self.origin),
+            scheme_and_host(&other.origin),
+        ) {
+            (Some((scheme, host)), Some((other_scheme, other_host))) => {
+                scheme.eq_ignore_ascii_case(other_scheme)
+                    && registrable_domain(host)
+                        .eq_ignore_ascii_case(registrable_domain(other_host))
+            }
+            _ => false,
+        }
+    }
+}
+
+fn scheme_and_host(origin: &str) -> Option<(&str, &str)> {
+    let (scheme, authority) = origin.split_once("://")?;
+    let host = if authority.starts_with('[') {
+        // IPv6 address, which contains colons of its own
+        authority.split_inclusive(']').next().unwrap_or(authority)
+    } else {
+        authority.split(':').next().unwrap_or(authority)
+    };
+    Some((scheme, host))
+}
+
+fn registrable_domain(host: &str) -> &str {
+    let host = host.trim_end_matches('.');
+    // IP addresses do not have a registrable domain; the whole host has to match
+    if host.starts_with('[') || host.parse::<std::net::Ipv4Addr>().is_ok() {
+        return host;
+    }
+
+    let mut dots = host.rmatch_indices('.').map(|(idx, _)| idx);
+    let last_two = match (dots.next(), dots.next()) {
+        (Some(_), Some(idx)) => &host[idx + 1..],
+        _ => return host,
+    };
+    let is_multi_label_suffix = MULTI_LABEL_SUFFIXES
+        .iter()
+        .any(|suffix| suffix.eq_ignore_ascii_case(last_two));
+    if is_multi_label_suffix {
+        match dots.next() {
+            Some(idx) => &host[idx + 1..],
+            None => host,
+        }
+    } else {
+        last_two
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{registrable_domain, scheme_and_host};
+
+    #[test]
+    fn registrable_domain_strips_subdomains() {
+        assert_eq!(registrable_domain("example.com"), "example.com");
+        assert_eq!(registrable_domain("app.example.com"), "example.com");
+        assert_eq!(registrable_domain("a.b.example.com"), "example.com");
+        assert_eq!(registrable_domain("localhost"), "localhost");
+    }
+
+    #[test]
+    fn registrable_domain_respects_multi_label_suffixes() {
+        assert_eq!(registrable_domain("www.example.co.uk"), "example.co.uk");
+        assert_eq!(registrable_domain("example.co.uk"), "example.co.uk");
+        assert_eq!(registrable_domain("co.uk"), "co.uk");
+        assert_eq!(registrable_domain("me.github.io"), "me.github.io");
+    }
+
+    #[test]
+    fn registrable_domain_keeps_ip_addresses() {
+        assert_eq!(registrable_domain("127.0.0.1"), "127.0.0.1");
+        assert_eq!(registrable_domain("[::1]"), "[::1]");
+    }
+
+    #[test]
+    fn scheme_and_host_strips_port() {
+        assert_eq!(
+            scheme_and_host("https://example.com:8080"),
+            Some(("https", "example.com"))
+        );
+        assert_eq!(
+            scheme_and_host("http://[::1]:3000"),
+            Some(("http", "[::1]"))
+        );
+        assert_eq!(scheme_and_host("null"), None);
+    }
+}
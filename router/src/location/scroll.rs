@@ -0,0 +1,105 @@
+use std::{fmt, sync::Arc};
+use tachys::dom::window;
+use web_sys::{Element, ScrollToOptions};
+
+/// How the router scrolls the page at the end of a navigation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollBehavior {
+    /// Jump straight to the new scroll position.
+    #[default]
+    Auto,
+    /// Animate smoothly to the new scroll position.
+    Smooth,
+    /// Never scroll. The app is responsible for restoring the scroll position itself.
+    Manual,
+}
+
+impl From<ScrollBehavior> for web_sys::ScrollBehavior {
+    fn from(value: ScrollBehavior) -> Self {
+        match value {
+            ScrollBehavior::Smooth => web_sys::ScrollBehavior::Smooth,
+            ScrollBehavior::Auto | ScrollBehavior::Manual => {
+                web_sys::ScrollBehavior::Auto
+            }
+        }
+    }
+}
+
+/// Configures how the router scrolls the page at the end of a navigation, either to the element
+/// targeted by the URL's hash or to the top of the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollConfig {
+    /// How the page should be scrolled.
+    pub behavior: ScrollBehavior,
+    /// The distance, in pixels, to leave above an element targeted by the URL's hash. This is
+    /// useful when the page has a fixed header.
+    pub offset_y: f64,
+    /// The distance, in pixels, to leave to the left of an element targeted by the URL's hash.
+    pub offset_x: f64,
+}
+
+impl ScrollConfig {
+    /// Scrolls to the given element, or to the top of the page if there is none.
+    pub(crate) fn scroll_to(&self, el: Option<&Element>) {
+        if self.behavior == ScrollBehavior::Manual {
+            return;
+        }
+
+        let window = window();
+        let options = ScrollToOptions::new();
+        options.set_behavior(self.behavior.into());
+        match el {
+            Some(el) => {
+                let rect = el.get_bounding_client_rect();
+                let scroll_x = window.scroll_x().unwrap_or_default();
+                let scroll_y = window.scroll_y().unwrap_or_default();
+                options.set_left(rect.left() + scroll_x - self.offset_x);
+                options.set_top(rect.top() + scroll_y - self.offset_y);
+            }
+            None => {
+                options.set_left(0.0);
+                options.set_top(0.0);
+            }
+        }
+        window.scroll_to_with_scroll_to_options(&options);
+    }
+}
+
+/// Configures whether the router saves the scroll position of each history entry, and restores it
+/// when the user goes back or forward to that entry.
+///
+/// When enabled, the position is saved in the entry's
+/// [`history.state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) as each new
+/// navigation starts, and restored once the routes for the entry have rendered. This replaces the
+/// browser's own scroll restoration, which usually runs before an async route has loaded. The
+/// position cannot be saved alongside a history state that is not an object, like the JSON string
+/// used by [`use_history_state`](crate::hooks::use_history_state).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScrollRestoration {
+    /// Whether scroll positions are saved and restored.
+    ///
+    /// Defaults to `false`.
+    pub auto: bool,
+}
+
+/// Takes over scrolling at the end of each navigation from the router's [`ScrollConfig`].
+///
+/// The function is called with the element targeted by the URL's hash, or with `None` if the
+/// router would otherwise scroll to the top of the page. Provide this via context in a component
+/// that wraps the `<Router/>`.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct OverrideScroll(pub Arc<dyn Fn(Option<&Element>) + Send + Sync>);
+
+impl OverrideScroll {
+    /// Creates a new scroll override.
+    pub fn new(f: impl Fn(Option<&Element>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for OverrideScroll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverrideScroll").finish_non_exhaustive()
+    }
+}
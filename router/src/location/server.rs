@@ -44,13 +44,60 @@ impl RequestUrl {
             search: url.query().unwrap_or_default().to_string(),
             search_params,
             hash: Default::default(),
+            username: Some(url.username())
+                .filter(|username| !username.is_empty())
+                .map(String::from),
+            password: url.password().map(String::from),
         })
     }
 }
 
+/// The origin of the request being rendered on the server, like `https://example.com`.
+///
+/// Server integrations provide this via context, based on the request's `Host` header, so that
+/// the router's URL has the correct origin during server rendering. Without it, URLs are
+/// resolved against a placeholder origin.
+///
+/// Because the `Host` header is controlled by the client, a `RequestOrigin` can only be created
+/// from a valid `http` or `https` origin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestOrigin(Arc<str>);
+
+impl RequestOrigin {
+    /// Creates a request origin from a full origin, like `https://example.com`.
+    ///
+    /// Returns `None` if `origin` is not a valid `http` or `https` origin, or if it contains
+    /// anything other than a scheme, host, and port.
+    pub fn new(origin: &str) -> Option<Self> {
+        let url = url::Url::parse(origin).ok()?;
+        let is_origin = matches!(url.scheme(), "http" | "https")
+            && url.has_host()
+            && url.username().is_empty()
+            && url.password().is_none()
+            && url.path() == "/"
+            && url.query().is_none()
+            && url.fragment().is_none();
+        is_origin.then(|| Self(url.origin().ascii_serialization().into()))
+    }
+
+    /// Creates a request origin from a scheme (like `https`) and the value of a `Host` header
+    /// (like `example.com:8080`).
+    ///
+    /// Returns `None` if they do not form a valid origin. See [`RequestOrigin::new`].
+    pub fn from_host(scheme: &str, host: &str) -> Option<Self> {
+        Self::new(&format!("{scheme}://{host}"))
+    }
+}
+
+impl AsRef<str> for RequestOrigin {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::RequestUrl;
+    use super::{RequestOrigin, RequestUrl};
 
     #[test]
     pub fn should_parse_url_without_origin() {
@@ -64,6 +111,30 @@ mod tests {
         assert_eq!(url.path(), "/foo/bar");
     }
 
+    #[cfg(feature = "ssr")]
+    #[test]
+    pub fn should_parse_with_request_origin() {
+        let origin =
+            RequestOrigin::from_host("https", "example.com:8080").unwrap();
+        let url = RequestUrl::new("/foo?bar=baz")
+            .parse_with_base(origin.as_ref())
+            .unwrap();
+        assert_eq!(url.to_string(), "https://example.com:8080/foo?bar=baz");
+    }
+
+    #[test]
+    pub fn should_reject_invalid_request_origins() {
+        assert!(RequestOrigin::from_host("https", "a b").is_none());
+        assert!(RequestOrigin::from_host("x/y", "example.com").is_none());
+        assert!(RequestOrigin::from_host("ftp", "example.com").is_none());
+        assert!(RequestOrigin::from_host("https", "example.com/path").is_none());
+        assert!(RequestOrigin::from_host("https", "user@example.com").is_none());
+        assert_eq!(
+            RequestOrigin::new("https://Example.com/").unwrap().as_ref(),
+            "https://example.com"
+        );
+    }
+
     #[test]
     pub fn should_parse_with_base() {
         let url = RequestUrl::new("https://www.example.com/foo/bar")
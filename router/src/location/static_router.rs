@@ -0,0 +1,84 @@
+use super::{LocationChange, LocationProvider, Url, BASE};
+use reactive_graph::signal::{ArcRwSignal, ReadSignal};
+use std::borrow::Cow;
+
+/// A [`LocationProvider`] that always stays at a single URL, and never navigates.
+///
+/// This is intended for static site generation and other workloads that render each path once
+/// on the server: it does not listen for any browser events or touch `window.history`. To
+/// simulate a series of navigations instead, see [`MemoryRouter`](super::MemoryRouter).
+#[derive(Clone, Debug)]
+pub struct StaticRouter {
+    url: ArcRwSignal<Url>,
+}
+
+impl StaticRouter {
+    /// Creates a location provider that stays at the given URL.
+    pub fn new_with_url(url: Url) -> Self {
+        Self {
+            url: ArcRwSignal::new(url),
+        }
+    }
+
+    /// Creates a location provider that stays at the given path or URL.
+    pub fn parse_url(url: &str) -> Result<Self, url::ParseError> {
+        Self::parse(url).map(Self::new_with_url)
+    }
+}
+
+impl LocationProvider for StaticRouter {
+    type Error = url::ParseError;
+
+    fn new() -> Result<Self, Self::Error> {
+        Self::parse_url("/")
+    }
+
+    fn as_url(&self) -> &ArcRwSignal<Url> {
+        &self.url
+    }
+
+    /// There is no global location for a static router, so this always returns the root URL.
+    /// Use [`as_url`](LocationProvider::as_url) to get the URL of a given router.
+    fn current() -> Result<Url, Self::Error> {
+        Self::parse("/")
+    }
+
+    fn init(&self, _base: Option<Cow<'static, str>>) {}
+
+    fn ready_to_complete(&self) {}
+
+    fn complete_navigation(&self, _loc: &LocationChange) {}
+
+    fn parse_with_base(url: &str, base: &str) -> Result<Url, Self::Error> {
+        let base = url::Url::parse(base)?;
+        let url = url::Url::options().base_url(Some(&base)).parse(url)?;
+        Ok(Url::from_parsed(&url))
+    }
+
+    fn parse(url: impl AsRef<str>) -> Result<Url, Self::Error> {
+        Self::parse_with_base(url.as_ref(), BASE)
+    }
+
+    /// A static router never navigates, so redirects are ignored.
+    fn redirect(_loc: &str) {}
+
+    fn is_back(&self) -> ReadSignal<bool> {
+        ArcRwSignal::new(false).read_only().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaticRouter;
+    use crate::location::{LocationChange, LocationProvider};
+    use reactive_graph::traits::GetUntracked;
+
+    #[test]
+    fn never_navigates() {
+        let router = StaticRouter::parse_url("/about").unwrap();
+        router.complete_navigation(&LocationChange::to("/contact").build());
+        router.ready_to_complete();
+        assert_eq!(router.as_url().get_untracked().path(), "/about");
+        assert!(!router.is_back().get_untracked());
+    }
+}
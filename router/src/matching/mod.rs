@@ -11,6 +11,7 @@ mod nested;
 mod vertical;
 use crate::{static_routes::RegenerationFn, Method, SsrMode};
 pub use horizontal::*;
+use nested::any_nested_match::{AnyNestedMatch, IntoAnyNestedMatch};
 pub use nested::*;
 use std::{borrow::Cow, collections::HashSet, sync::atomic::Ordering};
 pub use vertical::*;
@@ -110,6 +111,22 @@ pub trait MatchInterface {
     fn as_matched(&self) -> &str;
 
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>);
+
+    /// Takes the routes that were matched for the named outlets of this route. See
+    /// [`MatchNestedRoutes::match_named_outlets`].
+    fn take_named_outlets(&mut self) -> Vec<(&'static str, AnyNestedMatch)> {
+        Vec::new()
+    }
+
+    /// The callbacks that run when the router enters or leaves this route.
+    fn lifecycle(&self) -> RouteLifecycle {
+        RouteLifecycle::default()
+    }
+
+    /// The title of this route, if it has one.
+    fn title(&self) -> Option<Cow<'static, str>> {
+        None
+    }
 }
 
 pub trait MatchParams {
@@ -118,7 +135,7 @@ pub trait MatchParams {
 
 pub trait MatchNestedRoutes {
     type Data;
-    type Match: MatchInterface + MatchParams;
+    type Match: MatchInterface + MatchParams + 'static;
 
     /// Matches nested routes
     ///
@@ -144,6 +161,33 @@ pub trait MatchNestedRoutes {
     ) -> impl IntoIterator<Item = GeneratedRouteData> + '_;
 
     fn optional(&self) -> bool;
+
+    /// The name of the outlet in the parent route that this route is rendered into, or `None` if
+    /// it is rendered into the parent's default outlet.
+    ///
+    /// Routes with an outlet name are skipped when matching the default outlet, and are matched
+    /// by [`match_named_outlets`](Self::match_named_outlets) instead.
+    fn outlet_name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Matches the routes that are rendered into named outlets against `path`, adding the first
+    /// match for each outlet name that does not have one yet to `matches`.
+    fn match_named_outlets(
+        &self,
+        path: &str,
+        matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+    ) {
+        let Some(outlet) = self.outlet_name() else {
+            return;
+        };
+        if matches.iter().any(|(name, _)| *name == outlet) {
+            return;
+        }
+        if let (Some((_, matched)), _) = self.match_nested(path) {
+            matches.push((outlet, matched.into_any_nested_match()));
+        }
+    }
 }
 
 #[derive(Default, Debug, PartialEq)]
@@ -152,6 +196,7 @@ pub struct GeneratedRouteData {
     pub ssr_mode: SsrMode,
     pub methods: HashSet<Method>,
     pub regenerate: Vec<RegenerationFn>,
+    pub name: Option<Cow<'static, str>>,
 }
 
 #[cfg(test)]
@@ -161,7 +206,7 @@ mod tests {
         matching::MatchParams, MatchInterface, PathSegment, StaticSegment,
         WildcardSegment,
     };
-    use either_of::{Either, EitherOf4};
+    use either_of::{Either, EitherOf3, EitherOf4};
 
     #[test]
     pub fn matches_single_root_route() {
@@ -213,6 +258,50 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn matches_named_outlets_in_parallel() {
+        let routes: RouteDefs<_> = RouteDefs::new(
+            NestedRoute::new(StaticSegment("/dashboard"), || ()).child((
+                NestedRoute::new(StaticSegment(""), || "Main"),
+                NestedRoute::new(StaticSegment(""), || "Sidebar")
+                    .outlet(Some("sidebar")),
+                NestedRoute::new(StaticSegment("stats"), || "Stats"),
+            )),
+        );
+
+        let mut matched = routes.match_route("/dashboard").unwrap();
+        let named = matched.take_named_outlets();
+        assert_eq!(
+            named.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            ["sidebar"]
+        );
+        let (_, child) = MatchInterface::into_view_and_child(matched);
+        assert!(matches!(child, Some(EitherOf3::A(_))));
+
+        let mut matched = routes.match_route("/dashboard/stats").unwrap();
+        assert!(matched.take_named_outlets().is_empty());
+        let (_, child) = MatchInterface::into_view_and_child(matched);
+        assert!(matches!(child, Some(EitherOf3::C(_))));
+
+        // routes in named outlets are left out of the generated routes
+        let (_, paths) = routes.generate_routes();
+        assert_eq!(paths.into_iter().count(), 2);
+    }
+
+    #[test]
+    pub fn carries_route_titles_into_matches() {
+        let routes: RouteDefs<_> = RouteDefs::new(
+            NestedRoute::new(StaticSegment("/users"), || ())
+                .title(Some("Users".into()))
+                .child(NestedRoute::new(StaticSegment("settings"), || ())),
+        );
+
+        let matched = routes.match_route("/users/settings").unwrap();
+        assert_eq!(matched.title().as_deref(), Some("Users"));
+        let (_, child) = MatchInterface::into_view_and_child(matched);
+        assert_eq!(child.unwrap().title(), None);
+    }
+
     #[test]
     pub fn does_not_match_route_unless_full_param_matches() {
         let routes = RouteDefs::<_>::new((
@@ -1,4 +1,5 @@
 #![allow(clippy::type_complexity)]
+use super::RouteLifecycle;
 use crate::{
     matching::any_choose_view::AnyChooseView, ChooseView, MatchInterface,
     MatchParams, RouteMatchId,
@@ -14,8 +15,23 @@ pub struct AnyNestedMatch {
     as_matched: for<'a> fn(&'a ErasedLocal) -> &'a str,
     into_view_and_child:
         fn(ErasedLocal) -> (AnyChooseView, Option<AnyNestedMatch>),
+    take_named_outlets:
+        fn(&mut ErasedLocal) -> Vec<(&'static str, AnyNestedMatch)>,
+    lifecycle: fn(&ErasedLocal) -> RouteLifecycle,
+    title: fn(&ErasedLocal) -> Option<Cow<'static, str>>,
 }
 
+/// Compares the ID, matched path, and params of each match, but not their nested matches.
+impl PartialEq for AnyNestedMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_id() == other.as_id()
+            && self.as_matched() == other.as_matched()
+            && self.to_params() == other.to_params()
+    }
+}
+
+impl Eq for AnyNestedMatch {}
+
 impl Debug for AnyNestedMatch {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AnyNestedMatch").finish_non_exhaustive()
@@ -67,12 +83,33 @@ where
             )
         }
 
+        fn take_named_outlets<T: MatchInterface + 'static>(
+            value: &mut ErasedLocal,
+        ) -> Vec<(&'static str, AnyNestedMatch)> {
+            value.get_mut::<T>().take_named_outlets()
+        }
+
+        fn lifecycle<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> RouteLifecycle {
+            value.get_ref::<T>().lifecycle()
+        }
+
+        fn title<T: MatchInterface + 'static>(
+            value: &ErasedLocal,
+        ) -> Option<Cow<'static, str>> {
+            value.get_ref::<T>().title()
+        }
+
         AnyNestedMatch {
             value,
             to_params: to_params::<T>,
             as_id: as_id::<T>,
             as_matched: as_matched::<T>,
             into_view_and_child: into_view_and_child::<T>,
+            take_named_outlets: take_named_outlets::<T>,
+            lifecycle: lifecycle::<T>,
+            title: title::<T>,
         }
     }
 }
@@ -97,4 +134,16 @@ impl MatchInterface for AnyNestedMatch {
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         (self.into_view_and_child)(self.value)
     }
+
+    fn take_named_outlets(&mut self) -> Vec<(&'static str, AnyNestedMatch)> {
+        (self.take_named_outlets)(&mut self.value)
+    }
+
+    fn lifecycle(&self) -> RouteLifecycle {
+        (self.lifecycle)(&self.value)
+    }
+
+    fn title(&self) -> Option<Cow<'static, str>> {
+        (self.title)(&self.value)
+    }
 }
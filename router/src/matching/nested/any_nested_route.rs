@@ -18,6 +18,9 @@ pub struct AnyNestedRoute {
             -> (Option<(RouteMatchId, AnyNestedMatch)>, &'a str),
     generate_routes: fn(&Erased) -> Vec<GeneratedRouteData>,
     optional: fn(&Erased) -> bool,
+    outlet_name: fn(&Erased) -> Option<&'static str>,
+    match_named_outlets:
+        fn(&Erased, &str, &mut Vec<(&'static str, AnyNestedMatch)>),
 }
 
 impl Clone for AnyNestedRoute {
@@ -81,12 +84,30 @@ where
             value.get_ref::<T>().optional()
         }
 
+        fn outlet_name<T: MatchNestedRoutes + Send + Clone + 'static>(
+            value: &Erased,
+        ) -> Option<&'static str> {
+            value.get_ref::<T>().outlet_name()
+        }
+
+        fn match_named_outlets<
+            T: MatchNestedRoutes + Send + Clone + 'static,
+        >(
+            value: &Erased,
+            path: &str,
+            matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+        ) {
+            value.get_ref::<T>().match_named_outlets(path, matches)
+        }
+
         AnyNestedRoute {
             value: Erased::new(self),
             clone: clone::<T>,
             match_nested: match_nested::<T>,
             generate_routes: generate_routes::<T>,
             optional: optional::<T>,
+            outlet_name: outlet_name::<T>,
+            match_named_outlets: match_named_outlets::<T>,
         }
     }
 }
@@ -109,4 +130,16 @@ impl MatchNestedRoutes for AnyNestedRoute {
     fn optional(&self) -> bool {
         (self.optional)(&self.value)
     }
+
+    fn outlet_name(&self) -> Option<&'static str> {
+        (self.outlet_name)(&self.value)
+    }
+
+    fn match_named_outlets(
+        &self,
+        path: &str,
+        matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+    ) {
+        (self.match_named_outlets)(&self.value, path, matches)
+    }
 }
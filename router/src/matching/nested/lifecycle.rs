@@ -0,0 +1,158 @@
+use crate::params::ParamsMap;
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+/// The future returned by a route lifecycle callback.
+pub type RouteLifecycleFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// A callback that runs when the router enters a route, once the route's data has loaded.
+///
+/// It is called with the params matched by the route, and the navigation does not complete until
+/// the future it returns has resolved. It can be created from any
+/// `Fn(ParamsMap) -> impl Future<Output = ()>`.
+#[derive(Clone)]
+pub struct OnRouteEnter(
+    Arc<dyn Fn(ParamsMap) -> RouteLifecycleFuture + Send + Sync>,
+);
+
+impl<F, Fut> From<F> for OnRouteEnter
+where
+    F: Fn(ParamsMap) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(Arc::new(move |params| Box::pin(f(params))))
+    }
+}
+
+/// A callback that runs when the router leaves a route, before the route that replaces it is
+/// loaded.
+///
+/// The navigation does not complete until the future it returns has resolved. It can be created
+/// from any `Fn() -> impl Future<Output = ()>`.
+#[derive(Clone)]
+pub struct OnRouteLeave(Arc<dyn Fn() -> RouteLifecycleFuture + Send + Sync>);
+
+impl<F, Fut> From<F> for OnRouteLeave
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(Arc::new(move || Box::pin(f())))
+    }
+}
+
+/// The callbacks that run when the router enters or leaves a route.
+///
+/// Two lifecycles are equal if they share the same callbacks.
+#[derive(Clone, Default)]
+pub struct RouteLifecycle {
+    on_enter: Option<OnRouteEnter>,
+    on_leave: Option<OnRouteLeave>,
+}
+
+impl RouteLifecycle {
+    /// Sets the callback that runs when the router enters the route.
+    pub fn on_enter(mut self, on_enter: Option<OnRouteEnter>) -> Self {
+        self.on_enter = on_enter;
+        self
+    }
+
+    /// Sets the callback that runs when the router leaves the route.
+    pub fn on_leave(mut self, on_leave: Option<OnRouteLeave>) -> Self {
+        self.on_leave = on_leave;
+        self
+    }
+
+    /// Calls the `on_enter` callback, if there is one.
+    pub fn enter(&self, params: ParamsMap) -> Option<RouteLifecycleFuture> {
+        self.on_enter.as_ref().map(|on_enter| (on_enter.0)(params))
+    }
+
+    /// Calls the `on_leave` callback, if there is one.
+    pub fn leave(&self) -> Option<RouteLifecycleFuture> {
+        self.on_leave.as_ref().map(|on_leave| (on_leave.0)())
+    }
+}
+
+impl fmt::Debug for RouteLifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteLifecycle")
+            .field("on_enter", &self.on_enter.is_some())
+            .field("on_leave", &self.on_leave.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for RouteLifecycle {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T: ?Sized>(a: Option<&Arc<T>>, b: Option<&Arc<T>>) -> bool {
+            match (a, b) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+        }
+
+        same(
+            self.on_enter.as_ref().map(|f| &f.0),
+            other.on_enter.as_ref().map(|f| &f.0),
+        ) && same(
+            self.on_leave.as_ref().map(|f| &f.0),
+            other.on_leave.as_ref().map(|f| &f.0),
+        )
+    }
+}
+
+impl Eq for RouteLifecycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::RouteLifecycle;
+    use crate::params::ParamsMap;
+    use futures::executor::block_on;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn calls_callbacks() {
+        let entered = Arc::new(AtomicUsize::new(0));
+        let left = Arc::new(AtomicUsize::new(0));
+        let lifecycle = RouteLifecycle::default()
+            .on_enter(Some(
+                {
+                    let entered = Arc::clone(&entered);
+                    move |_: ParamsMap| {
+                        let entered = Arc::clone(&entered);
+                        async move {
+                            entered.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                .into(),
+            ))
+            .on_leave(Some(
+                {
+                    let left = Arc::clone(&left);
+                    move || {
+                        let left = Arc::clone(&left);
+                        async move {
+                            left.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                .into(),
+            ));
+
+        block_on(lifecycle.enter(ParamsMap::new()).unwrap());
+        block_on(lifecycle.leave().unwrap());
+        assert_eq!(entered.load(Ordering::Relaxed), 1);
+        assert_eq!(left.load(Ordering::Relaxed), 1);
+
+        assert_eq!(lifecycle.clone(), lifecycle);
+        assert_ne!(lifecycle, RouteLifecycle::default());
+        assert!(RouteLifecycle::default().enter(ParamsMap::new()).is_none());
+    }
+}
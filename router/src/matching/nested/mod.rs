@@ -3,19 +3,24 @@ use super::{
     PartialPathMatch, PathSegment, PossibleRouteMatch, RouteMatchId,
 };
 use crate::{ChooseView, GeneratedRouteData, MatchParams, Method, SsrMode};
+use any_nested_match::AnyNestedMatch;
 use core::{fmt, iter};
 use either_of::Either;
 use std::{
     borrow::Cow,
     collections::HashSet,
+    mem,
     sync::atomic::{AtomicU16, Ordering},
 };
 use tachys::prelude::IntoMaybeErased;
 
 pub mod any_nested_match;
 pub mod any_nested_route;
+mod lifecycle;
 mod tuples;
 
+pub use lifecycle::*;
+
 pub(crate) static ROUTE_ID: AtomicU16 = AtomicU16::new(1);
 
 #[derive(Debug, PartialEq, Eq)]
@@ -27,6 +32,10 @@ pub struct NestedRoute<Segments, Children, Data, View> {
     view: View,
     methods: HashSet<Method>,
     ssr_mode: SsrMode,
+    name: Option<Cow<'static, str>>,
+    outlet: Option<&'static str>,
+    lifecycle: RouteLifecycle,
+    title: Option<Cow<'static, str>>,
 }
 
 impl<Segments, Children, Data, View> IntoMaybeErased
@@ -71,6 +80,10 @@ where
             view: self.view.clone(),
             methods: self.methods.clone(),
             ssr_mode: self.ssr_mode.clone(),
+            name: self.name.clone(),
+            outlet: self.outlet,
+            lifecycle: self.lifecycle.clone(),
+            title: self.title.clone(),
         }
     }
 }
@@ -96,6 +109,10 @@ impl<Segments, View> NestedRoute<Segments, (), (), View> {
             view: view.into_maybe_erased(),
             methods: [Method::Get].into(),
             ssr_mode: Default::default(),
+            name: None,
+            outlet: None,
+            lifecycle: RouteLifecycle::default(),
+            title: None,
         }
     }
 }
@@ -112,6 +129,10 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             view,
             ssr_mode,
             methods,
+            name,
+            outlet,
+            lifecycle,
+            title,
             ..
         } = self;
         NestedRoute {
@@ -122,6 +143,10 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             view,
             ssr_mode,
             methods,
+            name,
+            outlet,
+            lifecycle,
+            title,
         }
     }
 
@@ -129,6 +154,37 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
         self.ssr_mode = ssr_mode;
         self
     }
+
+    /// Sets a name for this route, which can be used to generate a path to it.
+    pub fn name(mut self, name: Option<Cow<'static, str>>) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the name of the outlet in the parent route that this route is rendered into. If this
+    /// is `None`, it is rendered into the parent's default outlet.
+    pub fn outlet(mut self, outlet: Option<&'static str>) -> Self {
+        self.outlet = outlet;
+        self
+    }
+
+    /// Sets a title for this route, which is used as its label in breadcrumbs.
+    pub fn title(mut self, title: Option<Cow<'static, str>>) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Sets a callback that runs when the router enters this route, once its data has loaded.
+    pub fn on_enter(mut self, on_enter: Option<OnRouteEnter>) -> Self {
+        self.lifecycle = self.lifecycle.on_enter(on_enter);
+        self
+    }
+
+    /// Sets a callback that runs when the router leaves this route.
+    pub fn on_leave(mut self, on_leave: Option<OnRouteLeave>) -> Self {
+        self.lifecycle = self.lifecycle.on_leave(on_leave);
+        self
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -140,6 +196,10 @@ pub struct NestedMatch<Child, View> {
     params: Vec<(Cow<'static, str>, String)>,
     /// The nested route.
     child: Option<Child>,
+    /// The nested routes matched for each of this route's named outlets.
+    named: Vec<(&'static str, AnyNestedMatch)>,
+    lifecycle: RouteLifecycle,
+    title: Option<Cow<'static, str>>,
     view_fn: View,
 }
 
@@ -152,6 +212,7 @@ where
             .field("matched", &self.matched)
             .field("params", &self.params)
             .field("child", &self.child)
+            .field("named", &self.named)
             .finish()
     }
 }
@@ -181,6 +242,18 @@ where
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         (self.view_fn, self.child)
     }
+
+    fn take_named_outlets(&mut self) -> Vec<(&'static str, AnyNestedMatch)> {
+        mem::take(&mut self.named)
+    }
+
+    fn lifecycle(&self) -> RouteLifecycle {
+        self.lifecycle.clone()
+    }
+
+    fn title(&self) -> Option<Cow<'static, str>> {
+        self.title.clone()
+    }
 }
 
 impl<Segments, Children, Data, View> MatchNestedRoutes
@@ -201,6 +274,10 @@ where
             && self.children.as_ref().map(|n| n.optional()).unwrap_or(true)
     }
 
+    fn outlet_name(&self) -> Option<&'static str> {
+        self.outlet
+    }
+
     fn match_nested<'a>(
         &'a self,
         path: &'a str,
@@ -217,12 +294,21 @@ where
                      mut params,
                      matched,
                  }| {
+                    let mut named = Vec::new();
                     let (_, inner, remaining, was_optional_fallback) =
                         match &self.children {
                             None => (None, None, remaining, false),
                             Some(children) => {
+                                // children in named outlets are matched in parallel with the
+                                // child in the default outlet, against the same path
+                                children
+                                    .match_named_outlets(remaining, &mut named);
                                 let (inner, remaining) =
-                                    children.match_nested(remaining);
+                                    if children.outlet_name().is_some() {
+                                        (None, remaining)
+                                    } else {
+                                        children.match_nested(remaining)
+                                    };
 
                                 match inner {
                                     Some((id, inner)) => (
@@ -231,6 +317,11 @@ where
                                         remaining,
                                         false,
                                     ),
+                                    // if all of the children are in named outlets, matching
+                                    // any of them is enough
+                                    None if !named.is_empty() => {
+                                        (None, None, "", false)
+                                    }
                                     None if this_was_optional => {
                                         // if the parent route was optional, re-match children against full path
                                         let (inner, remaining) =
@@ -277,6 +368,9 @@ where
                                     matched: matched.to_string(),
                                     params,
                                     child: inner,
+                                    named,
+                                    lifecycle: self.lifecycle.clone(),
+                                    title: self.title.clone(),
                                     view_fn: self.view.clone(),
                                 },
                             )),
@@ -298,6 +392,10 @@ where
         let children = self.children.as_ref();
         let ssr_mode = self.ssr_mode.clone();
         let methods = self.methods.clone();
+        let name = self.name.clone();
+        // a route in a named outlet is displayed at the same URL as a route in the default
+        // outlet, so it does not add any routes of its own
+        let in_named_outlet = self.outlet.is_some();
         let regenerate = match &ssr_mode {
             SsrMode::Static(data) => match data.regenerate.as_ref() {
                 None => vec![],
@@ -306,16 +404,24 @@ where
             _ => vec![],
         };
 
-        match children {
+        let routes = match children {
             None => Either::Left(iter::once(GeneratedRouteData {
                 segments: segment_routes,
                 ssr_mode,
                 methods,
                 regenerate,
+                name,
             })),
             Some(children) => {
                 Either::Right(children.generate_routes().into_iter().map(
                     move |child| {
+                        // an index route (one with an empty path) shares its parent's name, if it
+                        // does not have one of its own
+                        let is_index = child
+                            .segments
+                            .iter()
+                            .all(|segment| segment.as_raw_str().is_empty());
+
                         // extend this route's segments with child segments
                         let segments = segment_routes
                             .clone()
@@ -329,12 +435,17 @@ where
                         let mut regenerate = regenerate.clone();
                         regenerate.extend(child.regenerate);
 
+                        let name = child.name.or_else(|| {
+                            is_index.then(|| name.clone()).flatten()
+                        });
+
                         if child.ssr_mode > ssr_mode {
                             GeneratedRouteData {
                                 segments,
                                 ssr_mode: child.ssr_mode,
                                 methods,
                                 regenerate,
+                                name,
                             }
                         } else {
                             GeneratedRouteData {
@@ -342,11 +453,13 @@ where
                                 ssr_mode: ssr_mode.clone(),
                                 methods,
                                 regenerate,
+                                name,
                             }
                         }
                     },
                 ))
             }
-        }
+        };
+        routes.filter(move |_| !in_named_outlet)
     }
 }
@@ -1,4 +1,7 @@
-use super::{MatchInterface, MatchNestedRoutes, PathSegment, RouteMatchId};
+use super::{
+    any_nested_match::AnyNestedMatch, MatchInterface, MatchNestedRoutes,
+    PathSegment, RouteLifecycle, RouteMatchId,
+};
 use crate::{ChooseView, GeneratedRouteData, MatchParams};
 use core::iter;
 use either_of::*;
@@ -78,6 +81,18 @@ where
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
         self.0.into_view_and_child()
     }
+
+    fn take_named_outlets(&mut self) -> Vec<(&'static str, AnyNestedMatch)> {
+        self.0.take_named_outlets()
+    }
+
+    fn lifecycle(&self) -> RouteLifecycle {
+        self.0.lifecycle()
+    }
+
+    fn title(&self) -> Option<Cow<'static, str>> {
+        self.0.title()
+    }
 }
 
 impl<A> MatchNestedRoutes for (A,)
@@ -103,6 +118,18 @@ where
     fn optional(&self) -> bool {
         self.0.optional()
     }
+
+    fn outlet_name(&self) -> Option<&'static str> {
+        self.0.outlet_name()
+    }
+
+    fn match_named_outlets(
+        &self,
+        path: &str,
+        matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+    ) {
+        self.0.match_named_outlets(path, matches)
+    }
 }
 
 impl<A, B> MatchParams for Either<A, B>
@@ -151,6 +178,27 @@ where
             }
         }
     }
+
+    fn take_named_outlets(&mut self) -> Vec<(&'static str, AnyNestedMatch)> {
+        match self {
+            Either::Left(i) => i.take_named_outlets(),
+            Either::Right(i) => i.take_named_outlets(),
+        }
+    }
+
+    fn lifecycle(&self) -> RouteLifecycle {
+        match self {
+            Either::Left(i) => i.lifecycle(),
+            Either::Right(i) => i.lifecycle(),
+        }
+    }
+
+    fn title(&self) -> Option<Cow<'static, str>> {
+        match self {
+            Either::Left(i) => i.title(),
+            Either::Right(i) => i.title(),
+        }
+    }
 }
 
 impl<A, B> MatchNestedRoutes for (A, B)
@@ -167,15 +215,28 @@ where
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
         #[allow(non_snake_case)]
         let (A, B) = &self;
-        if let (Some((id, matched)), remaining) = A.match_nested(path) {
-            return (Some((id, Either::Left(matched))), remaining);
+        if A.outlet_name().is_none() {
+            if let (Some((id, matched)), remaining) = A.match_nested(path) {
+                return (Some((id, Either::Left(matched))), remaining);
+            }
         }
-        if let (Some((id, matched)), remaining) = B.match_nested(path) {
-            return (Some((id, Either::Right(matched))), remaining);
+        if B.outlet_name().is_none() {
+            if let (Some((id, matched)), remaining) = B.match_nested(path) {
+                return (Some((id, Either::Right(matched))), remaining);
+            }
         }
         (None, path)
     }
 
+    fn match_named_outlets(
+        &self,
+        path: &str,
+        matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+    ) {
+        self.0.match_named_outlets(path, matches);
+        self.1.match_named_outlets(path, matches);
+    }
+
     fn generate_routes(
         &self,
     ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
@@ -205,7 +266,7 @@ where
         &'a self,
         path: &'a str,
     ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
-        for item in self.iter() {
+        for item in self.iter().filter(|item| item.outlet_name().is_none()) {
             if let (Some((id, matched)), remaining) = item.match_nested(path) {
                 return (Some((id, matched)), remaining);
             }
@@ -213,6 +274,16 @@ where
         (None, path)
     }
 
+    fn match_named_outlets(
+        &self,
+        path: &str,
+        matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+    ) {
+        for item in self.iter() {
+            item.match_named_outlets(path, matches);
+        }
+    }
+
     fn generate_routes(
         &self,
     ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
@@ -280,6 +351,26 @@ macro_rules! tuples {
                     })*
                 }
             }
+
+            fn take_named_outlets(
+                &mut self,
+            ) -> Vec<(&'static str, AnyNestedMatch)> {
+                match self {
+                    $($either::$ty(i) => i.take_named_outlets(),)*
+                }
+            }
+
+            fn lifecycle(&self) -> RouteLifecycle {
+                match self {
+                    $($either::$ty(i) => i.lifecycle(),)*
+                }
+            }
+
+            fn title(&self) -> Option<Cow<'static, str>> {
+                match self {
+                    $($either::$ty(i) => i.title(),)*
+                }
+            }
         }
 
         impl<$($ty),*> MatchNestedRoutes for ($($ty,)*)
@@ -300,12 +391,24 @@ macro_rules! tuples {
                 #[allow(non_snake_case)]
 
                 let ($($ty,)*) = &self;
-                $(if let (Some((_, matched)), remaining) = $ty.match_nested(path) {
-                    return (Some((RouteMatchId($count), $either::$ty(matched))), remaining);
+                $(if $ty.outlet_name().is_none() {
+                    if let (Some((_, matched)), remaining) = $ty.match_nested(path) {
+                        return (Some((RouteMatchId($count), $either::$ty(matched))), remaining);
+                    }
                 })*
                 (None, path)
             }
 
+            fn match_named_outlets(
+                &self,
+                path: &str,
+                matches: &mut Vec<(&'static str, AnyNestedMatch)>,
+            ) {
+                #[allow(non_snake_case)]
+                let ($($ty,)*) = &self;
+                $($ty.match_named_outlets(path, matches);)*
+            }
+
             fn generate_routes(
                 &self,
             ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
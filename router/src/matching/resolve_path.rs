@@ -9,7 +9,8 @@ pub fn resolve_path<'a>(
         path.into()
     } else {
         let base_path = normalize(base, false);
-        let from_path = from.map(|from| normalize(from, false));
+        let from_path =
+            from.map(|from| normalize(strip_replaced(from, path), false));
         let result = if let Some(from_path) = from_path {
             if path.starts_with('/') {
                 base_path
@@ -25,14 +26,77 @@ pub fn resolve_path<'a>(
         let result_empty = result.is_empty();
         let prefix = if result_empty { "/".into() } else { result };
 
-        prefix + normalize(path, result_empty)
+        let resolved = prefix + normalize(path, result_empty);
+        if has_dot_segments(&resolved) {
+            collapse_dots(&resolved).into()
+        } else {
+            resolved
+        }
+    }
+}
+
+/// Removes the parts of `from` that are replaced by `path`, if `path` is only a query string or
+/// only a hash. As in the browser, a query string replaces both the query string and the hash of
+/// `from`, while a hash only replaces its hash.
+fn strip_replaced<'a>(from: &'a str, path: &str) -> &'a str {
+    match path.chars().next() {
+        Some('?') => split_path(from).0,
+        Some('#') => from.split('#').next().unwrap_or_default(),
+        _ => from,
     }
 }
 
+/// Splits a path from any query string or hash that follows it.
+fn split_path(path: &str) -> (&str, &str) {
+    path.split_at(path.find(['?', '#']).unwrap_or(path.len()))
+}
+
+fn has_dot_segments(path: &str) -> bool {
+    split_path(path)
+        .0
+        .split('/')
+        .any(|segment| segment == "." || segment == "..")
+}
+
+/// Removes `.` segments from a path, and `..` segments along with the segment before each of
+/// them. Going up past the root stays at the root. Any query string or hash is left as-is.
+fn collapse_dots(path: &str) -> String {
+    let (path, rest) = split_path(path);
+    let mut segments = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let trailing_slash = !segments.is_empty() && path.ends_with('/');
+
+    let mut collapsed = String::with_capacity(path.len() + rest.len());
+    for segment in segments {
+        collapsed.push('/');
+        collapsed.push_str(segment);
+    }
+    if collapsed.is_empty() || trailing_slash {
+        collapsed.push('/');
+    }
+    collapsed.push_str(rest);
+    collapsed
+}
+
+/// Schemes that are not followed by `//`, but still mark a path as a complete URL that should be
+/// left alone by the router.
+const OPAQUE_SCHEMES: [&str; 5] =
+    ["tel:", "mailto:", "data:", "javascript:", "blob:"];
+
 fn has_scheme(path: &str) -> bool {
     path.starts_with("//")
-        || path.starts_with("tel:")
-        || path.starts_with("mailto:")
+        || OPAQUE_SCHEMES.iter().any(|scheme| {
+            path.get(..scheme.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+        })
         || path
             .split_once("://")
             .map(|(prefix, _)| {
@@ -43,9 +107,14 @@ fn has_scheme(path: &str) -> bool {
             .unwrap_or(false)
 }
 
-#[doc(hidden)]
-fn normalize(path: &str, omit_slash: bool) -> Cow<'_, str> {
+/// Normalizes the slashes in a path, so that it can be appended to another normalized path.
+///
+/// The result has exactly one leading slash, unless it is empty, is only a query string or hash,
+/// or `omit_slash` is `true`. A run of trailing slashes is collapsed into one.
+pub(crate) fn normalize(path: &str, omit_slash: bool) -> Cow<'_, str> {
+    // drop every leading slash; one is added back below if needed
     let s = path.trim_start_matches('/');
+    // keep a single trailing slash, since `/foo/` and `/foo` can be different routes
     let trim_end = s
         .chars()
         .rev()
@@ -53,6 +122,8 @@ fn normalize(path: &str, omit_slash: bool) -> Cow<'_, str> {
         .count()
         .saturating_sub(1);
     let s = &s[0..s.len() - trim_end];
+    // a query string or hash is appended to the path before it as-is, and an empty path (which
+    // includes one made only of slashes) adds nothing
     if s.is_empty() || omit_slash || begins_with_query_or_hash(s) {
         s.into()
     } else {
@@ -83,6 +154,21 @@ fn remove_wildcard(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn resolve_path_leaves_opaque_schemes_alone() {
+        for path in [
+            "tel:+15555555555",
+            "mailto:greg@leptos.dev",
+            "data:text/plain;base64,SGVsbG8=",
+            "javascript:void(0)",
+            "JavaScript:void(0)",
+            "blob:https://leptos.dev/6f1c6f6e-3d5c-4b1a-9d8e-000000000000",
+        ] {
+            assert_eq!(resolve_path("/base", path, Some("/page")), path);
+        }
+        assert_eq!(resolve_path("", "database", Some("/")), "/database");
+    }
+
     #[test]
     fn normalize_query_string_with_opening_slash() {
         assert_eq!(normalize("/?foo=bar", false), "?foo=bar");
@@ -97,4 +183,62 @@ mod tests {
     fn normalize_dedup_trailing_slashes() {
         assert_eq!(normalize("foo/bar/////", false), "/foo/bar/");
     }
+
+    #[test]
+    fn normalize_empty_and_slash_only() {
+        assert_eq!(normalize("", false), "");
+        assert_eq!(normalize("////", false), "");
+        assert_eq!(normalize("////", true), "");
+    }
+
+    #[test]
+    fn normalize_query_or_hash_without_path() {
+        assert_eq!(normalize("?q=1", false), "?q=1");
+        assert_eq!(normalize("#anchor", false), "#anchor");
+        assert_eq!(normalize("//#anchor", false), "#anchor");
+    }
+
+    #[test]
+    fn normalize_omit_slash() {
+        assert_eq!(normalize("/foo//", true), "foo/");
+    }
+
+    #[test]
+    fn resolve_path_query_or_hash_only() {
+        assert_eq!(resolve_path("", "?new=1", Some("/page")), "/page?new=1");
+        assert_eq!(
+            resolve_path("", "?new=1", Some("/page?old=1#top")),
+            "/page?new=1"
+        );
+        assert_eq!(
+            resolve_path("", "#section", Some("/page?old=1#top")),
+            "/page?old=1#section"
+        );
+        assert_eq!(
+            resolve_path("/app", "?new=1", Some("/app/page")),
+            "/app/page?new=1"
+        );
+    }
+
+    #[test]
+    fn resolve_path_parent_segments() {
+        assert_eq!(
+            resolve_path("", "../../sibling", Some("/a/b/c")),
+            "/a/sibling"
+        );
+        assert_eq!(resolve_path("", "./child", Some("/a")), "/a/child");
+        assert_eq!(resolve_path("", "/a/../b", None), "/b");
+    }
+
+    #[test]
+    fn collapse_dots_clamps_to_root() {
+        assert_eq!(collapse_dots("/a/../../.."), "/");
+        assert_eq!(collapse_dots("/../a"), "/a");
+    }
+
+    #[test]
+    fn collapse_dots_keeps_query_and_hash() {
+        assert_eq!(collapse_dots("/a/b/..?q=../x"), "/a?q=../x");
+        assert_eq!(collapse_dots("/a/./b#../c"), "/a/b#../c");
+    }
 }
@@ -1,4 +1,20 @@
-use crate::location::State;
+use crate::location::{LocationChange, State, Url};
+use futures::{channel::oneshot, future};
+use leptos::leptos_dom::helpers::{set_timeout_with_handle, TimeoutHandle};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{traits::Set, wrappers::write::SignalSetter};
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use thiserror::Error;
 
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::hooks::use_navigate).
 #[derive(Clone, Debug)]
@@ -11,18 +27,633 @@ pub struct NavigateOptions {
     /// If `true`, the router will scroll to the top of the window at the end of navigation.
     /// Defaults to `true`.
     pub scroll: bool,
+    /// The inverse of [`scroll`](Self::scroll), named as in React Router: if `true`, the router
+    /// does not scroll to the top of the window at the end of navigation, whatever `scroll` is
+    /// set to. Defaults to `false`.
+    #[deprecated(note = "use `scroll` instead")]
+    pub prevent_scroll_reset: bool,
     /// [State](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that should be pushed
     /// onto the history stack during navigation.
     pub state: State,
+    /// The `id` of an element to scroll into view at the end of navigation. This takes priority
+    /// over both the URL's hash and `scroll`.
+    pub scroll_target: Option<String>,
+    /// If set, the navigation waits for this long before it happens, and is canceled with
+    /// [`NavigationError::Cancelled`] if another debounced navigation starts in the meantime.
+    /// Defaults to the router's
+    /// [`navigation_debounce`](crate::location::BrowserUrlOptions::navigation_debounce), which
+    /// is `None`.
+    pub debounce: Option<Duration>,
 }
 
 impl Default for NavigateOptions {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             resolve: true,
             replace: false,
             scroll: true,
+            prevent_scroll_reset: false,
             state: State::new(None),
+            scroll_target: None,
+            debounce: None,
         }
     }
 }
+
+impl NavigateOptions {
+    /// Describes a navigation to `value` with these options.
+    #[allow(deprecated)]
+    pub(crate) fn into_location_change(self, value: String) -> LocationChange {
+        LocationChange::to(value)
+            .replace(self.replace)
+            .scroll(self.scroll && !self.prevent_scroll_reset)
+            .state(self.state)
+            .scroll_target(self.scroll_target)
+            .build()
+    }
+
+    /// The options that describe a navigation started by a link or form, which has already been
+    /// resolved to `loc`.
+    #[allow(deprecated)]
+    pub(crate) fn from_location_change(loc: &LocationChange) -> Self {
+        Self {
+            resolve: false,
+            replace: loc.replace,
+            scroll: loc.scroll,
+            prevent_scroll_reset: false,
+            state: loc.state.clone(),
+            scroll_target: loc.scroll_target.clone(),
+            debounce: None,
+        }
+    }
+}
+
+/// The reason a navigation started with [`use_navigate`](crate::hooks::use_navigate) did not
+/// happen.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NavigationError {
+    /// A navigation guard denied the navigation.
+    #[error("navigation was rejected by a navigation guard")]
+    GuardRejected,
+    /// The URL being navigated to could not be parsed.
+    #[error("could not parse URL: {0}")]
+    ParseError(String),
+    /// Another navigation started before this one finished.
+    #[error("navigation was cancelled")]
+    Cancelled,
+}
+
+/// The result of a navigation started with [`use_navigate`](crate::hooks::use_navigate).
+///
+/// The navigation happens whether or not this is awaited. Await it to find out whether the
+/// navigation succeeded, or call [`forget`](Self::forget) to ignore the outcome.
+pub struct NavigationOutcome(
+    Pin<Box<dyn Future<Output = Result<(), NavigationError>>>>,
+);
+
+impl NavigationOutcome {
+    /// Creates an outcome that is already known.
+    pub(crate) fn ready(result: Result<(), NavigationError>) -> Self {
+        Self(Box::pin(future::ready(result)))
+    }
+
+    /// Creates an outcome that will be known once the given future resolves.
+    pub(crate) fn pending(
+        fut: impl Future<Output = Result<(), NavigationError>> + 'static,
+    ) -> Self {
+        Self(Box::pin(fut))
+    }
+
+    /// Ignores the outcome of the navigation. The navigation still happens.
+    pub fn forget(self) {}
+}
+
+impl Debug for NavigationOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NavigationOutcome").finish_non_exhaustive()
+    }
+}
+
+impl Future for NavigationOutcome {
+    type Output = Result<(), NavigationError>;
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+type PendingWait = Option<(Option<TimeoutHandle>, oneshot::Sender<()>)>;
+
+/// Delays navigations, so that only the last of several that start in quick succession happens.
+///
+/// A router has a single debouncer, shared by its context and its location provider, so that
+/// navigations from [`use_navigate`](crate::hooks::use_navigate) and from link clicks supersede
+/// one another.
+#[derive(Clone, Default)]
+pub(crate) struct Debouncer(Arc<Mutex<PendingWait>>);
+
+impl Debouncer {
+    /// Starts waiting for `delay`, superseding any earlier wait that has not finished yet.
+    ///
+    /// The returned receiver resolves once the delay is up, or is canceled if another wait
+    /// starts before then.
+    pub(crate) fn wait(&self, delay: Duration) -> oneshot::Receiver<()> {
+        let rx = self.supersede();
+        let pending = Arc::clone(&self.0);
+        let timeout = set_timeout_with_handle(
+            move || {
+                let tx = pending.lock().or_poisoned().take().map(|(_, tx)| tx);
+                if let Some(tx) = tx {
+                    _ = tx.send(());
+                }
+            },
+            delay,
+        );
+        let mut pending = self.0.lock().or_poisoned();
+        match timeout {
+            Ok(handle) => {
+                if let Some((timeout, _)) = pending.as_mut() {
+                    *timeout = Some(handle);
+                }
+            }
+            Err(_) => {
+                if let Some((_, tx)) = pending.take() {
+                    _ = tx.send(());
+                }
+            }
+        }
+        rx
+    }
+
+    /// Cancels any earlier wait that has not finished yet, and registers a new one.
+    fn supersede(&self) -> oneshot::Receiver<()> {
+        let (tx, rx) = oneshot::channel();
+        let previous = self.0.lock().or_poisoned().replace((None, tx));
+        // dropping the previous sender cancels the earlier wait
+        if let Some((Some(handle), _)) = previous {
+            handle.clear();
+        }
+        rx
+    }
+}
+
+impl Debug for Debouncer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debouncer").finish_non_exhaustive()
+    }
+}
+
+/// A navigation that is about to happen, as passed to a guard registered with
+/// [`use_before_navigate`](crate::hooks::use_before_navigate).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavigationTarget {
+    /// The current URL.
+    pub from: Url,
+    /// The URL that is being navigated to.
+    pub to: Url,
+}
+
+/// The decision made by a navigation guard about whether a navigation should happen.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum NavigationDecision {
+    /// The navigation should continue.
+    #[default]
+    Allow,
+    /// The navigation should be canceled, leaving the URL unchanged.
+    Deny,
+    /// The navigation should go to the given path instead. Guards are not run again for the
+    /// redirected navigation.
+    Redirect(String),
+}
+
+/// The decision made by a [`NavigationInterceptor`] about whether a navigation should happen.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InterceptorDecision {
+    /// The navigation should continue, to the next interceptor and then to any navigation guards.
+    #[default]
+    Continue,
+    /// The navigation should go to the given path instead. Interceptors and guards are not run
+    /// again for the redirected navigation.
+    Redirect(String),
+    /// The navigation should be canceled, leaving the URL unchanged.
+    Abort,
+}
+
+impl From<InterceptorDecision> for NavigationDecision {
+    fn from(value: InterceptorDecision) -> Self {
+        match value {
+            InterceptorDecision::Continue => NavigationDecision::Allow,
+            InterceptorDecision::Redirect(path) => {
+                NavigationDecision::Redirect(path)
+            }
+            InterceptorDecision::Abort => NavigationDecision::Deny,
+        }
+    }
+}
+
+/// Hooks into each client-side navigation, so that libraries (for authentication, analytics, and
+/// so on) can observe, redirect, or cancel navigations without depending on how the router is
+/// set up. Register one with
+/// [`provide_navigation_interceptor`](crate::hooks::provide_navigation_interceptor).
+///
+/// This is implemented for any `Fn(&str, &NavigateOptions) -> InterceptorDecision`.
+pub trait NavigationInterceptor: Send + Sync + 'static {
+    /// Called before the router updates the URL, with the path being navigated to (including its
+    /// query string and hash) and the options for the navigation.
+    fn before(
+        &self,
+        to: &str,
+        options: &NavigateOptions,
+    ) -> InterceptorDecision;
+}
+
+impl<F> NavigationInterceptor for F
+where
+    F: Fn(&str, &NavigateOptions) -> InterceptorDecision
+        + Send
+        + Sync
+        + 'static,
+{
+    fn before(
+        &self,
+        to: &str,
+        options: &NavigateOptions,
+    ) -> InterceptorDecision {
+        self(to, options)
+    }
+}
+
+type NavigationGuardFn =
+    Arc<dyn Fn(NavigationTarget) -> NavigationDecision + Send + Sync>;
+
+type AsyncNavigationGuardFn = Arc<
+    dyn Fn(
+            NavigationTarget,
+        ) -> Pin<Box<dyn Future<Output = NavigationDecision>>>
+        + Send
+        + Sync,
+>;
+
+#[derive(Clone)]
+enum NavigationGuard {
+    Sync(NavigationGuardFn),
+    Async(AsyncNavigationGuardFn),
+}
+
+type NavigationGuardList = Mutex<Vec<(usize, NavigationGuard)>>;
+type NavigationInterceptorList =
+    Mutex<Vec<(usize, Arc<dyn NavigationInterceptor>)>>;
+
+/// The set of navigation guards registered with a router.
+#[derive(Clone, Default)]
+pub(crate) struct NavigationGuards {
+    next_id: Arc<AtomicUsize>,
+    guards: Arc<NavigationGuardList>,
+    interceptors: Arc<NavigationInterceptorList>,
+    set_is_routing: Arc<Mutex<Option<SignalSetter<bool>>>>,
+}
+
+impl Debug for NavigationGuards {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NavigationGuards")
+            .field("len", &self.guards.lock().or_poisoned().len())
+            .field(
+                "interceptors",
+                &self.interceptors.lock().or_poisoned().len(),
+            )
+            .finish()
+    }
+}
+
+impl NavigationGuards {
+    /// Adds a guard, which will run until the returned handle is dropped.
+    pub fn register(
+        &self,
+        guard: impl Fn(NavigationTarget) -> NavigationDecision
+            + Send
+            + Sync
+            + 'static,
+    ) -> NavigationGuardHandle {
+        self.register_guard(NavigationGuard::Sync(Arc::new(guard)))
+    }
+
+    /// Adds an async guard, which will run until the returned handle is dropped.
+    pub fn register_async(
+        &self,
+        guard: impl Fn(
+                NavigationTarget,
+            ) -> Pin<Box<dyn Future<Output = NavigationDecision>>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> NavigationGuardHandle {
+        self.register_guard(NavigationGuard::Async(Arc::new(guard)))
+    }
+
+    fn register_guard(&self, guard: NavigationGuard) -> NavigationGuardHandle {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.guards.lock().or_poisoned().push((id, guard));
+        NavigationGuardHandle {
+            id,
+            guards: Arc::downgrade(&self.guards),
+        }
+    }
+
+    /// Adds an interceptor, which will run until it is removed with
+    /// [`remove_interceptor`](Self::remove_interceptor) using the returned id.
+    pub fn register_interceptor(
+        &self,
+        interceptor: impl NavigationInterceptor,
+    ) -> usize {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.interceptors
+            .lock()
+            .or_poisoned()
+            .push((id, Arc::new(interceptor)));
+        id
+    }
+
+    /// Removes an interceptor added with [`register_interceptor`](Self::register_interceptor).
+    pub fn remove_interceptor(&self, id: usize) {
+        self.interceptors
+            .lock()
+            .or_poisoned()
+            .retain(|(entry, _)| *entry != id);
+    }
+
+    /// Runs each interceptor in the order in which it was registered, stopping at the first one
+    /// that does not let the navigation continue.
+    pub fn intercept(
+        &self,
+        to: &str,
+        options: &NavigateOptions,
+    ) -> InterceptorDecision {
+        // clone the interceptors out, so that one can register or remove others
+        let interceptors = self
+            .interceptors
+            .lock()
+            .or_poisoned()
+            .iter()
+            .map(|(_, interceptor)| Arc::clone(interceptor))
+            .collect::<Vec<_>>();
+        interceptors
+            .iter()
+            .map(|interceptor| interceptor.before(to, options))
+            .find(|decision| *decision != InterceptorDecision::Continue)
+            .unwrap_or_default()
+    }
+
+    /// Sets the signal that is set to `true` while async guards are running.
+    pub fn set_is_routing(&self, set_is_routing: Option<SignalSetter<bool>>) {
+        *self.set_is_routing.lock().or_poisoned() = set_is_routing;
+    }
+
+    /// Whether any async guards are registered, in which case the navigation must wait for
+    /// [`check_async`](Self::check_async).
+    pub fn has_async(&self) -> bool {
+        self.guards
+            .lock()
+            .or_poisoned()
+            .iter()
+            .any(|(_, guard)| matches!(guard, NavigationGuard::Async(_)))
+    }
+
+    // clone the guards out, so that a guard can register or remove guards without deadlocking
+    fn snapshot(&self) -> Vec<NavigationGuard> {
+        self.guards
+            .lock()
+            .or_poisoned()
+            .iter()
+            .map(|(_, guard)| guard.clone())
+            .collect()
+    }
+
+    /// Runs each synchronous guard in the order in which it was registered, stopping at the first
+    /// one that does not allow the navigation. Async guards are skipped.
+    pub fn check(&self, target: &NavigationTarget) -> NavigationDecision {
+        self.snapshot()
+            .iter()
+            .filter_map(|guard| match guard {
+                NavigationGuard::Sync(guard) => Some(guard(target.clone())),
+                NavigationGuard::Async(_) => None,
+            })
+            .find(|decision| *decision != NavigationDecision::Allow)
+            .unwrap_or_default()
+    }
+
+    /// Runs each guard, sync or async, in the order in which it was registered, stopping at the
+    /// first one that does not allow the navigation.
+    ///
+    /// While async guards are running, the router's `set_is_routing` signal is set to `true`. It
+    /// is only set back to `false` here if the navigation will not go ahead.
+    pub fn check_async(
+        &self,
+        target: NavigationTarget,
+    ) -> impl Future<Output = NavigationDecision> + 'static {
+        let guards = self.snapshot();
+        let set_is_routing = *self.set_is_routing.lock().or_poisoned();
+        async move {
+            let mut waited = false;
+            let mut decision = NavigationDecision::Allow;
+            for guard in guards {
+                decision = match guard {
+                    NavigationGuard::Sync(guard) => guard(target.clone()),
+                    NavigationGuard::Async(guard) => {
+                        if !waited {
+                            waited = true;
+                            if let Some(set_is_routing) = set_is_routing {
+                                set_is_routing.set(true);
+                            }
+                        }
+                        guard(target.clone()).await
+                    }
+                };
+                if decision != NavigationDecision::Allow {
+                    break;
+                }
+            }
+            if waited && decision != NavigationDecision::Allow {
+                if let Some(set_is_routing) = set_is_routing {
+                    set_is_routing.set(false);
+                }
+            }
+            decision
+        }
+    }
+}
+
+/// A handle to a navigation guard registered with
+/// [`use_before_navigate`](crate::hooks::use_before_navigate). The guard is removed when this is
+/// dropped.
+#[must_use = "the navigation guard is removed as soon as its handle is dropped"]
+#[derive(Debug)]
+pub struct NavigationGuardHandle {
+    id: usize,
+    guards: Weak<NavigationGuardList>,
+}
+
+impl Drop for NavigationGuardHandle {
+    fn drop(&mut self) {
+        if let Some(guards) = self.guards.upgrade() {
+            guards.lock().or_poisoned().retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Debouncer, InterceptorDecision, NavigateOptions, NavigationDecision,
+        NavigationGuards, NavigationTarget,
+    };
+    use crate::location::LocationChange;
+    use futures::channel::oneshot;
+
+    fn target() -> NavigationTarget {
+        NavigationTarget {
+            from: Default::default(),
+            to: Default::default(),
+        }
+    }
+
+    #[test]
+    fn navigate_and_link_click_share_a_debounce_window() {
+        // the router context (used by `use_navigate`) and the location provider (used for link
+        // clicks) hold clones of the same debouncer
+        let router_debouncer = Debouncer::default();
+        let provider_debouncer = router_debouncer.clone();
+
+        let mut navigate = router_debouncer.supersede();
+        let mut link_click = provider_debouncer.supersede();
+        assert!(matches!(navigate.try_recv(), Err(oneshot::Canceled)));
+        assert!(matches!(link_click.try_recv(), Ok(None)));
+
+        let mut navigate = router_debouncer.supersede();
+        assert!(matches!(link_click.try_recv(), Err(oneshot::Canceled)));
+        assert!(matches!(navigate.try_recv(), Ok(None)));
+    }
+
+    #[test]
+    fn guards_run_in_order_until_one_does_not_allow() {
+        let guards = NavigationGuards::default();
+        let _allow = guards.register(|_| NavigationDecision::Allow);
+        let _redirect =
+            guards.register(|_| NavigationDecision::Redirect("/login".into()));
+        let _deny = guards.register(|_| NavigationDecision::Deny);
+        assert_eq!(
+            guards.check(&target()),
+            NavigationDecision::Redirect("/login".into())
+        );
+    }
+
+    #[test]
+    fn async_guards_run_in_order_with_sync_guards() {
+        let guards = NavigationGuards::default();
+        let _allow = guards.register(|_| NavigationDecision::Allow);
+        let _deny = guards
+            .register_async(|_| Box::pin(async { NavigationDecision::Deny }));
+        let _redirect =
+            guards.register(|_| NavigationDecision::Redirect("/login".into()));
+        assert!(guards.has_async());
+        // async guards are skipped by the synchronous check
+        assert_eq!(
+            guards.check(&target()),
+            NavigationDecision::Redirect("/login".into())
+        );
+        assert_eq!(
+            futures::executor::block_on(guards.check_async(target())),
+            NavigationDecision::Deny
+        );
+    }
+
+    #[test]
+    fn dropping_handle_removes_guard() {
+        let guards = NavigationGuards::default();
+        let deny = guards.register(|_| NavigationDecision::Deny);
+        assert_eq!(guards.check(&target()), NavigationDecision::Deny);
+        drop(deny);
+        assert_eq!(guards.check(&target()), NavigationDecision::Allow);
+    }
+
+    #[test]
+    fn interceptors_run_in_order_until_one_does_not_continue() {
+        let guards = NavigationGuards::default();
+        guards.register_interceptor(|_: &str, _: &NavigateOptions| {
+            InterceptorDecision::Continue
+        });
+        let login =
+            guards.register_interceptor(|to: &str, _: &NavigateOptions| {
+                if to.starts_with("/admin") {
+                    InterceptorDecision::Redirect("/login".into())
+                } else {
+                    InterceptorDecision::Continue
+                }
+            });
+        guards.register_interceptor(|_: &str, options: &NavigateOptions| {
+            if options.replace {
+                InterceptorDecision::Abort
+            } else {
+                InterceptorDecision::Continue
+            }
+        });
+
+        let options = NavigateOptions::default();
+        assert_eq!(
+            guards.intercept("/admin", &options),
+            InterceptorDecision::Redirect("/login".into())
+        );
+        assert_eq!(
+            guards.intercept("/", &options),
+            InterceptorDecision::Continue
+        );
+        let replace = NavigateOptions {
+            replace: true,
+            ..Default::default()
+        };
+        assert_eq!(guards.intercept("/", &replace), InterceptorDecision::Abort);
+
+        guards.remove_interceptor(login);
+        assert_eq!(
+            guards.intercept("/admin", &options),
+            InterceptorDecision::Continue
+        );
+        assert_eq!(
+            NavigationDecision::from(InterceptorDecision::Abort),
+            NavigationDecision::Deny
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn options_map_to_location_change() {
+        let options = NavigateOptions {
+            replace: true,
+            scroll_target: Some("top".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.into_location_change("/a".into()),
+            LocationChange::to("/a")
+                .replace(true)
+                .scroll(true)
+                .scroll_target(Some("top".into()))
+                .build()
+        );
+
+        let options = NavigateOptions {
+            scroll: false,
+            ..Default::default()
+        };
+        assert!(!options.into_location_change("/a".into()).scroll);
+
+        let options = NavigateOptions {
+            prevent_scroll_reset: true,
+            ..Default::default()
+        };
+        assert!(!options.into_location_change("/a".into()).scroll);
+    }
+}
@@ -1,8 +1,13 @@
 use crate::{
     flat_router::MatchedRoute,
-    hooks::Matched,
-    location::{LocationProvider, Url},
-    matching::RouteDefs,
+    hooks::{
+        report_route_match, BreadcrumbItem, Matched, RouteAncestry, RouteDepth,
+    },
+    location::{LocationProvider, RouteError, Url},
+    matching::{
+        any_nested_match::AnyNestedMatch, RouteDefs, RouteLifecycle,
+        RouteLifecycleFuture,
+    },
     params::ParamsMap,
     view_transition::start_view_transition,
     ChooseView, MatchInterface, MatchNestedRoutes, MatchParams, PathSegment,
@@ -17,12 +22,15 @@ use reactive_graph::{
     computed::{ArcMemo, ScopedFuture},
     owner::{provide_context, use_context, Owner},
     signal::{ArcRwSignal, ArcTrigger},
-    traits::{Get, GetUntracked, Notify, ReadUntracked, Set, Track},
+    traits::{
+        Get, GetUntracked, Notify, ReadUntracked, Set, Track, WithUntracked,
+    },
     transition::AsyncTransition,
     wrappers::write::SignalSetter,
 };
 use send_wrapper::SendWrapper;
 use std::{
+    borrow::Cow,
     cell::RefCell,
     fmt::Debug,
     future::Future,
@@ -52,6 +60,8 @@ pub(crate) struct NestedRoutesView<Loc, Defs, FalFn> {
     pub fallback: FalFn,
     pub set_is_routing: Option<SignalSetter<bool>>,
     pub transition: bool,
+    pub breadcrumbs: ArcRwSignal<Vec<BreadcrumbItem>>,
+    pub route_error: ArcRwSignal<Option<RouteError>>,
 }
 
 /// Retained view state for the nested router.
@@ -84,6 +94,8 @@ where
             current_url,
             fallback,
             base,
+            breadcrumbs,
+            route_error,
             ..
         } = self;
 
@@ -94,6 +106,7 @@ where
 
         // match the route
         let new_match = routes.match_route(url.path());
+        report_route_match(&route_error, new_match.is_some());
 
         // start with an empty view because we'll be loading routes async
         let view = EitherOf3::A(()).build();
@@ -109,7 +122,8 @@ where
                     &outer_owner,
                 );
                 drop(url);
-                outer_owner.with(|| EitherOf3::C(Outlet().into_any()))
+                update_breadcrumbs(&breadcrumbs, &outlets);
+                outer_owner.with(|| EitherOf3::C(outlet_view(None).into_any()))
             }
         };
 
@@ -149,6 +163,7 @@ where
         state.path.push_str(url_snapshot.path());
 
         let new_match = self.routes.match_route(url_snapshot.path());
+        report_route_match(&self.route_error, new_match.is_some());
 
         state.current_url.set(url_snapshot);
 
@@ -156,9 +171,21 @@ where
             None => {
                 EitherOf3::<(), Fal, AnyView>::B((self.fallback)())
                     .rebuild(&mut state.view.borrow_mut());
+                let leaving = leave_routes(&state.outlets);
                 state.outlets.clear();
-                if let Some(loc) = self.location {
-                    loc.ready_to_complete();
+                update_breadcrumbs(&self.breadcrumbs, &state.outlets);
+                if leaving.is_empty() {
+                    if let Some(loc) = self.location {
+                        loc.ready_to_complete();
+                    }
+                } else {
+                    let location = self.location;
+                    Executor::spawn_local(async move {
+                        leave(leaving).await;
+                        if let Some(loc) = location {
+                            loc.ready_to_complete();
+                        }
+                    });
                 }
             }
             Some(route) => {
@@ -179,12 +206,16 @@ where
                     self.set_is_routing.is_some(),
                     0,
                 );
+                update_breadcrumbs(&self.breadcrumbs, &state.outlets);
 
                 let location = self.location.clone();
                 let is_back = location
                     .as_ref()
                     .map(|nav| nav.is_back().get_untracked())
                     .unwrap_or(false);
+                let transition_state = location
+                    .as_ref()
+                    .and_then(|nav| nav.view_transition_state());
                 Executor::spawn_local(async move {
                     let triggers = join_all(preloaders).await;
                     // tell each one of the outlet triggers that it's ready
@@ -194,7 +225,12 @@ where
                         }
                     };
                     if self.transition {
-                        start_view_transition(different_level, is_back, notify);
+                        start_view_transition(
+                            different_level,
+                            is_back,
+                            transition_state,
+                            notify,
+                        );
                     } else {
                         notify();
                     }
@@ -213,8 +249,10 @@ where
                 // if it was on the fallback, show the view instead
                 if matches!(state.view.borrow().state, EitherOf3::B(_)) {
                     self.outer_owner.with(|| {
-                        EitherOf3::<(), Fal, AnyView>::C(Outlet().into_any())
-                            .rebuild(&mut *state.view.borrow_mut());
+                        EitherOf3::<(), Fal, AnyView>::C(
+                            outlet_view(None).into_any(),
+                        )
+                        .rebuild(&mut *state.view.borrow_mut());
                     })
                 }
             }
@@ -323,12 +361,15 @@ where
                 current_url,
                 fallback,
                 base,
+                breadcrumbs,
+                route_error,
                 ..
             } = self;
             let current_url = current_url.read_untracked();
 
             let mut outlets = Vec::new();
             let new_match = routes.match_route(current_url.path());
+            report_route_match(&route_error, new_match.is_some());
             let view = match new_match {
                 None => Either::Left(fallback()),
                 Some(route) => {
@@ -340,6 +381,7 @@ where
                         &mut outlets,
                         &outer_owner,
                     );
+                    update_breadcrumbs(&breadcrumbs, &outlets);
 
                     // outlets will not send their views if the loaders are never polled
                     // the loaders are async so that they can lazy-load routes in the browser,
@@ -348,7 +390,8 @@ where
                         .now_or_never()
                         .expect("async routes not supported in SSR");
 
-                    outer_owner.with(|| Either::Right(Outlet().into_any()))
+                    outer_owner
+                        .with(|| Either::Right(outlet_view(None).into_any()))
                 }
             };
             view.to_html_with_buf(
@@ -377,12 +420,15 @@ where
             current_url,
             fallback,
             base,
+            breadcrumbs,
+            route_error,
             ..
         } = self;
         let current_url = current_url.read_untracked();
 
         let mut outlets = Vec::new();
         let new_match = routes.match_route(current_url.path());
+        report_route_match(&route_error, new_match.is_some());
         let view = match new_match {
             None => Either::Left(fallback()),
             Some(route) => {
@@ -394,6 +440,7 @@ where
                     &mut outlets,
                     &outer_owner,
                 );
+                update_breadcrumbs(&breadcrumbs, &outlets);
 
                 // outlets will not send their views if the loaders are never polled
                 // the loaders are async so that they can lazy-load routes in the browser,
@@ -402,7 +449,7 @@ where
                     .now_or_never()
                     .expect("async routes not supported in SSR");
 
-                outer_owner.with(|| Either::Right(Outlet().into_any()))
+                outer_owner.with(|| Either::Right(outlet_view(None).into_any()))
             }
         };
         view.to_html_async_with_buf::<OUT_OF_ORDER>(
@@ -425,6 +472,8 @@ where
             current_url,
             fallback,
             base,
+            breadcrumbs,
+            route_error,
             ..
         } = self;
 
@@ -435,6 +484,7 @@ where
 
         // match the route
         let new_match = routes.match_route(url.path());
+        report_route_match(&route_error, new_match.is_some());
 
         // start with an empty view because we'll be loading routes async
         let view = Rc::new(RefCell::new(
@@ -449,12 +499,14 @@ where
                         &outer_owner,
                     );
                     drop(url);
+                    update_breadcrumbs(&breadcrumbs, &outlets);
 
                     // TODO support for lazy hydration
                     join_all(mem::take(&mut loaders))
                         .now_or_never()
                         .expect("async routes not supported in SSR");
-                    outer_owner.with(|| EitherOf3::C(Outlet().into_any()))
+                    outer_owner
+                        .with(|| EitherOf3::C(outlet_view(None).into_any()))
                 }
             }
             .hydrate::<FROM_SERVER>(cursor, position),
@@ -484,6 +536,9 @@ pub(crate) struct RouteContext {
     pub matched: ArcRwSignal<String>,
     base: Option<Oco<'static, str>>,
     view_fn: Arc<Mutex<OutletViewFn>>,
+    named: NamedOutlets,
+    lifecycle: RouteLifecycle,
+    title: Option<Cow<'static, str>>,
 }
 
 impl Debug for RouteContext {
@@ -517,6 +572,228 @@ impl Clone for RouteContext {
             matched: self.matched.clone(),
             base: self.base.clone(),
             view_fn: Arc::clone(&self.view_fn),
+            named: self.named.clone(),
+            lifecycle: self.lifecycle.clone(),
+            title: self.title.clone(),
+        }
+    }
+}
+
+/// Updates the breadcrumb trail to match the routes that are currently matched, outermost first.
+fn update_breadcrumbs(
+    breadcrumbs: &ArcRwSignal<Vec<BreadcrumbItem>>,
+    routes: &[RouteContext],
+) {
+    let mut href = routes
+        .first()
+        .and_then(|route| route.base.as_deref())
+        .unwrap_or_default()
+        .trim_end_matches('/')
+        .to_string();
+    let trail = routes
+        .iter()
+        .filter_map(|route| {
+            route
+                .matched
+                .with_untracked(|matched| href.push_str(matched));
+            let label = route.title.as_deref()?;
+            Some(BreadcrumbItem {
+                label: label.to_string(),
+                href: if href.is_empty() {
+                    "/".to_string()
+                } else {
+                    href.clone()
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    if breadcrumbs.with_untracked(|current| *current != trail) {
+        breadcrumbs.set(trail);
+    }
+}
+
+/// Calls the `on_leave` callbacks of the given routes, and of the routes in their named outlets,
+/// from the innermost route outwards.
+fn leave_routes(routes: &[RouteContext]) -> Vec<RouteLifecycleFuture> {
+    routes
+        .iter()
+        .rev()
+        .flat_map(|route| {
+            let mut leaving = route.named.leave_all();
+            leaving.extend(route.lifecycle.leave());
+            leaving
+        })
+        .collect()
+}
+
+/// Waits for each of the `on_leave` futures in turn.
+async fn leave(leaving: Vec<RouteLifecycleFuture>) {
+    for leaving in leaving {
+        leaving.await;
+    }
+}
+
+/// The routes displayed in the named outlets of a route.
+///
+/// This is provided via context to the route's view, so that each `<Outlet name=.../>` in it can
+/// find the route matched for its name.
+#[derive(Clone, Default)]
+struct NamedOutlets {
+    trigger: ArcTrigger,
+    outlets: Arc<Mutex<Vec<NamedOutlet>>>,
+}
+
+struct NamedOutlet {
+    name: &'static str,
+    /// The owner of the routes in this outlet, which is cleaned up when they are removed.
+    owner: Owner,
+    /// The route that owns this outlet and its ancestors, followed by the route displayed in this
+    /// outlet and its descendants, so that routes in the outlet can read their parents' params.
+    routes: Vec<RouteContext>,
+    /// The index in `routes` of the route displayed in this outlet.
+    start: usize,
+}
+
+impl NamedOutlet {
+    fn build(
+        name: &'static str,
+        matched: AnyNestedMatch,
+        url: &Url,
+        base: Option<Oco<'static, str>>,
+        loaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
+        parents: &[RouteContext],
+        parent: &Owner,
+    ) -> Self {
+        let owner = parent.child();
+        let mut routes = parents.to_vec();
+        let start = routes.len();
+        matched.build_nested_route(url, base, loaders, &mut routes, &owner);
+        Self {
+            name,
+            owner,
+            routes,
+            start,
+        }
+    }
+}
+
+impl NamedOutlets {
+    /// The route displayed in the outlet with the given name, if there is one.
+    fn get(&self, name: &str) -> Option<RouteContext> {
+        self.trigger.track();
+        self.outlets
+            .lock()
+            .or_poisoned()
+            .iter()
+            .find(|outlet| outlet.name == name)
+            .map(|outlet| outlet.routes[outlet.start].clone())
+    }
+
+    /// Calls the `on_leave` callbacks of the routes in every named outlet.
+    fn leave_all(&self) -> Vec<RouteLifecycleFuture> {
+        self.outlets
+            .lock()
+            .or_poisoned()
+            .iter()
+            .flat_map(|outlet| leave_routes(&outlet.routes[outlet.start..]))
+            .collect()
+    }
+
+    /// Builds the routes matched for the named outlets of the last route in `parents`.
+    fn build(
+        &self,
+        matches: Vec<(&'static str, AnyNestedMatch)>,
+        url: &Url,
+        base: Option<Oco<'static, str>>,
+        loaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
+        parents: &[RouteContext],
+        parent: &Owner,
+    ) {
+        if matches.is_empty() {
+            return;
+        }
+        self.outlets
+            .lock()
+            .or_poisoned()
+            .extend(matches.into_iter().map(|(name, matched)| {
+                NamedOutlet::build(
+                    name,
+                    matched,
+                    url,
+                    base.clone(),
+                    loaders,
+                    parents,
+                    parent,
+                )
+            }));
+        self.trigger.notify();
+    }
+
+    /// Updates the named outlets of the last route in `parents` to show the new matches: outlets
+    /// that no longer have a match are emptied, and the rest are diffed like the default outlet.
+    #[allow(clippy::too_many_arguments)]
+    fn rebuild(
+        &self,
+        matches: Vec<(&'static str, AnyNestedMatch)>,
+        url: &Url,
+        base: Option<Oco<'static, str>>,
+        preloaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
+        full_loaders: &mut Vec<oneshot::Receiver<()>>,
+        parents: &[RouteContext],
+        parent: &Owner,
+        set_is_routing: bool,
+    ) {
+        let mut outlets = self.outlets.lock().or_poisoned();
+        let mut changed = false;
+        let mut leaving = Vec::new();
+        outlets.retain(|outlet| {
+            let matched = matches.iter().any(|(name, _)| *name == outlet.name);
+            if !matched {
+                leaving.extend(leave_routes(&outlet.routes[outlet.start..]));
+                outlet.owner.cleanup();
+                changed = true;
+            }
+            matched
+        });
+        if !leaving.is_empty() {
+            preloaders.push(Box::pin(async move {
+                leave(leaving).await;
+                ArcTrigger::new()
+            }));
+        }
+        for (name, matched) in matches {
+            match outlets.iter_mut().find(|outlet| outlet.name == name) {
+                Some(outlet) => {
+                    let mut items = outlet.start;
+                    matched.rebuild_nested_route(
+                        url,
+                        base.clone(),
+                        &mut items,
+                        preloaders,
+                        full_loaders,
+                        &mut outlet.routes,
+                        &outlet.owner,
+                        set_is_routing,
+                        0,
+                    );
+                }
+                None => {
+                    outlets.push(NamedOutlet::build(
+                        name,
+                        matched,
+                        url,
+                        base.clone(),
+                        preloaders,
+                        parents,
+                        parent,
+                    ));
+                    changed = true;
+                }
+            }
+        }
+        drop(outlets);
+        if changed {
+            self.trigger.notify();
         }
     }
 }
@@ -551,7 +828,7 @@ where
     Match: MatchInterface + MatchParams,
 {
     fn build_nested_route(
-        self,
+        mut self,
         url: &Url,
         base: Option<Oco<'static, str>>,
         loaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
@@ -559,6 +836,9 @@ where
         parent: &Owner,
     ) {
         let orig_url = url;
+        let named_matches = self.take_named_outlets();
+        let lifecycle = self.lifecycle();
+        let title = self.title();
 
         // each Outlet gets its own owner, so it can inherit context from its parent route,
         // a new owner will be constructed if a different route replaces this one in the outlet,
@@ -582,6 +862,14 @@ where
             .iter()
             .map(|route| (route.params.clone(), route.matched.clone()))
             .unzip();
+        let ancestry = RouteAncestry::new(
+            parent_params
+                .iter()
+                .cloned()
+                .zip(parent_matches.iter().cloned())
+                .chain(iter::once((params.clone(), matched.clone())))
+                .collect(),
+        );
         let params_including_parents = {
             let params = params.clone();
             ArcMemo::new({
@@ -615,6 +903,7 @@ where
         // storing a view in a signal would mean we need to keep a copy stored in the signal and
         // require that we can clone it out
         let trigger = ArcTrigger::new();
+        let depth = RouteDepth(outlets.len());
 
         // add this outlet to the end of the outlet stack used for diffing
         let outlet = RouteContext {
@@ -628,9 +917,24 @@ where
                 Suspend::new(Box::pin(async { ().into_any() }))
             }))),
             base: base.clone(),
+            named: NamedOutlets::default(),
+            lifecycle: lifecycle.clone(),
+            title,
         };
         outlets.push(outlet.clone());
 
+        // build the routes for this route's named outlets, which its view can display with
+        // <Outlet name=.../>
+        owner.with(|| provide_context(outlet.named.clone()));
+        outlet.named.build(
+            named_matches,
+            orig_url,
+            base.clone(),
+            loaders,
+            outlets,
+            &owner,
+        );
+
         // send the initial view through the channel, and recurse through the children
         let (view, child) = self.into_view_and_child();
 
@@ -638,13 +942,21 @@ where
             ScopedFuture::new({
                 let owner = outlet.owner.clone();
                 let url = outlet.url.clone();
+                let route_params = outlet.params.clone();
                 let matched = Matched(matched_including_parents);
                 let view_fn = Arc::clone(&outlet.view_fn);
                 async move {
                     provide_context(params_including_parents);
                     provide_context(url);
                     provide_context(matched.clone());
+                    provide_context(ancestry);
+                    provide_context(depth);
                     view.preload().await;
+                    if let Some(entering) =
+                        lifecycle.enter(route_params.get_untracked())
+                    {
+                        entering.await;
+                    }
                     *view_fn.lock().or_poisoned() =
                         Box::new(move |owner_where_used| {
                             owner.join_contexts(&owner_where_used);
@@ -692,7 +1004,7 @@ where
 
     #[allow(clippy::too_many_arguments)]
     fn rebuild_nested_route(
-        self,
+        mut self,
         url: &Url,
         base: Option<Oco<'static, str>>,
         items: &mut usize,
@@ -708,6 +1020,14 @@ where
             .take(*items)
             .map(|route| (route.params.clone(), route.matched.clone()))
             .unzip();
+        // if a different route replaces the one at this level, the router is leaving that route
+        // and every route below it
+        let leaving = match outlets.get(*items) {
+            Some(current) if current.id != self.as_id() => {
+                leave_routes(&outlets[*items..])
+            }
+            _ => Vec::new(),
+        };
         let current = outlets.get_mut(*items);
         match current {
             // if there's nothing currently in the routes at this point, build from here
@@ -725,6 +1045,9 @@ where
                 let new_params =
                     self.to_params().into_iter().collect::<ParamsMap>();
                 let new_match = self.as_matched().to_owned();
+                let named_matches = self.take_named_outlets();
+                let lifecycle = self.lifecycle();
+                let title = self.title();
 
                 let (view, child) = self.into_view_and_child();
 
@@ -752,6 +1075,17 @@ where
                         &mut current.matched,
                         ArcRwSignal::new(new_match),
                     );
+                    let ancestry = RouteAncestry::new(
+                        parent_params
+                            .iter()
+                            .cloned()
+                            .zip(parent_matches.iter().cloned())
+                            .chain(iter::once((
+                                current.params.clone(),
+                                current.matched.clone(),
+                            )))
+                            .collect(),
+                    );
                     let matched_including_parents = {
                         ArcMemo::new({
                             let matched = current.matched.clone();
@@ -782,6 +1116,14 @@ where
                     let mut old_owner =
                         Some(mem::replace(&mut current.owner, parent.child()));
                     let owner = current.owner.clone();
+
+                    // the routes in the old route's named outlets are cleaned up along with its
+                    // owner, so start over with new ones
+                    let named = NamedOutlets::default();
+                    current.named = named.clone();
+                    owner.with(|| provide_context(named.clone()));
+                    current.lifecycle = lifecycle.clone();
+                    current.title = title;
                     let (full_tx, full_rx) = oneshot::channel();
                     let full_tx = Mutex::new(Some(full_tx));
                     full_loaders.push(full_rx);
@@ -794,13 +1136,25 @@ where
                             let owner = owner.clone();
                             let trigger = current.trigger.clone();
                             let url = current.url.clone();
+                            let route_params = current.params.clone();
                             let matched = Matched(matched_including_parents);
+                            let depth = RouteDepth(*items);
                             let view_fn = Arc::clone(&current.view_fn);
                             async move {
+                                // the routes that are being replaced are left before the new
+                                // route is loaded
+                                leave(leaving).await;
                                 provide_context(params_including_parents);
                                 provide_context(url);
                                 provide_context(matched);
+                                provide_context(ancestry);
+                                provide_context(depth);
                                 view.preload().await;
+                                if let Some(entering) = lifecycle
+                                    .enter(route_params.get_untracked())
+                                {
+                                    entering.await;
+                                }
                                 *view_fn.lock().or_poisoned() =
                                     Box::new(move |owner_where_used| {
                                         owner.join_contexts(&owner_where_used);
@@ -849,6 +1203,15 @@ where
                     // if this match is different, all its children will also be different
                     outlets.truncate(*items + 1);
 
+                    named.build(
+                        named_matches,
+                        url,
+                        base.clone(),
+                        preloaders,
+                        outlets,
+                        &owner,
+                    );
+
                     // if this children has matches, then rebuild the lower section of the tree
                     if let Some(child) = child {
                         child.build_nested_route(
@@ -864,8 +1227,19 @@ where
                 current.matched.set(new_match);
                 current.params.set(new_params);
                 current.url.set(url.to_owned());
+                let named = current.named.clone();
+                let owner = current.owner.clone();
+                named.rebuild(
+                    named_matches,
+                    url,
+                    base.clone(),
+                    preloaders,
+                    full_loaders,
+                    &outlets[..=*items],
+                    &owner,
+                    set_is_routing,
+                );
                 if let Some(child) = child {
-                    let owner = current.owner.clone();
                     *items += 1;
                     child.rebuild_nested_route(
                         url,
@@ -913,18 +1287,41 @@ where
 
 /// Displays the child route nested in a parent route, allowing you to control exactly where
 /// that child route is displayed. Renders nothing if there is no nested child.
+///
+/// A route's view can contain more than one outlet, to display several child routes at the same
+/// URL (for example, a sidebar next to the main content). Each additional outlet needs a `name`,
+/// and displays the first child route with the same `outlet` prop that matches the URL. Child
+/// routes without an `outlet` prop are displayed in the unnamed outlet, as usual.
 #[component]
-pub fn Outlet() -> impl RenderHtml
-where
-{
+pub fn Outlet(
+    /// The name of this outlet. Child routes whose `outlet` prop is set to this name are
+    /// displayed here.
+    #[prop(optional)]
+    name: Option<&'static str>,
+) -> impl RenderHtml {
+    outlet_view(name)
+}
+
+fn outlet_view(name: Option<&'static str>) -> impl RenderHtml {
     move || {
-        let ctx = use_context::<RouteContext>()
-            .expect("<Outlet/> used without RouteContext being provided.");
-        let RouteContext {
-            trigger, view_fn, ..
-        } = ctx;
-        trigger.track();
-        let mut view_fn = view_fn.lock().or_poisoned();
-        view_fn(Owner::current().unwrap())
+        let ctx =
+            match name {
+                None => Some(use_context::<RouteContext>().expect(
+                    "<Outlet/> used without RouteContext being provided.",
+                )),
+                Some(name) => use_context::<NamedOutlets>()
+                    .expect("<Outlet name=.../> used outside of a route.")
+                    .get(name),
+            };
+        match ctx {
+            Some(RouteContext {
+                trigger, view_fn, ..
+            }) => {
+                trigger.track();
+                let mut view_fn = view_fn.lock().or_poisoned();
+                view_fn(Owner::current().unwrap())
+            }
+            None => Suspend::new(Box::pin(async { ().into_any() })),
+        }
     }
 }
@@ -5,7 +5,12 @@ use thiserror::Error;
 type ParamsMapInner = Vec<(Cow<'static, str>, Vec<String>)>;
 
 /// A key-value map of the current named route params and their values.
+///
+/// Keys are kept in the order in which they were first inserted, so a query string that is
+/// parsed into a map and serialized again with [`to_query_string`](Self::to_query_string) keeps
+/// its original order.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParamsMap(ParamsMapInner);
 
 impl ParamsMap {
@@ -36,6 +41,31 @@ impl ParamsMap {
         }
     }
 
+    /// Adds a value to the map and returns it, so that maps can be built up in a chain. If a value
+    /// with that key already exists, the new value will be added to it.
+    ///
+    /// Unlike [`insert`](Self::insert), the value is stored as-is, without being percent-decoded.
+    ///
+    /// ```rust
+    /// # use leptos_router::params::ParamsMap;
+    /// let map = ParamsMap::new().with("id", "1").with("tab", "posts");
+    /// assert_eq!(map.get_str("tab"), Some("posts"));
+    /// ```
+    pub fn with(
+        mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<String>,
+    ) -> Self {
+        let key = key.into();
+        let value = value.into();
+        if let Some(prev) = self.0.iter_mut().find(|(k, _)| k == &key) {
+            prev.1.push(value);
+        } else {
+            self.0.push((key, vec![value]));
+        }
+        self
+    }
+
     /// Inserts a value into the map, replacing any existing value for that key.
     pub fn replace(
         &mut self,
@@ -76,15 +106,77 @@ impl ParamsMap {
         })
     }
 
-    /// Removes a value from the map.
+    /// Gets the most-recently-added value of this param from the map, parsed into `T`.
+    ///
+    /// Returns [`ParamsError::MissingParam`] if there is no value for the key, or
+    /// [`ParamsError::InvalidParam`] if it cannot be parsed.
+    pub fn get_parsed<T>(&self, key: &str) -> Result<T, ParamsError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let value = self
+            .get_str(key)
+            .ok_or_else(|| ParamsError::MissingParam(key.to_string()))?;
+        T::from_str(value).map_err(|e| ParamsError::InvalidParam {
+            name: key.to_string(),
+            value: value.to_string(),
+            source: Arc::new(e),
+        })
+    }
+
+    /// Gets the most-recently-added value of this param from the map, parsed into `T`, or
+    /// `default` if there is no value or it cannot be parsed.
+    pub fn get_parsed_or<T>(&self, key: &str, default: T) -> T
+    where
+        T: FromStr,
+    {
+        self.get_str(key)
+            .and_then(|value| T::from_str(value).ok())
+            .unwrap_or(default)
+    }
+
+    /// Returns an iterator over every key-value pair in the map, including each value of a key
+    /// that appears more than once (as in `?tag=rust&tag=wasm`).
+    ///
+    /// Keys are visited in the order in which they were first inserted, and the values of each
+    /// key in the order in which they were added.
+    pub fn iter_all(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.0.iter().flat_map(|(k, vs)| {
+            vs.iter().map(move |v| (k.as_ref(), v.as_str()))
+        })
+    }
+
+    /// Removes a value from the map, keeping the order of the remaining keys.
     #[inline(always)]
     pub fn remove(&mut self, key: &str) -> Option<Vec<String>> {
-        for i in 0..self.0.len() {
-            if self.0[i].0 == key {
-                return Some(self.0.swap_remove(i).1);
+        let i = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(i).1)
+    }
+
+    /// Copies every key in `other` into this map, replacing all of the values of any key that is
+    /// in both. Keys that are new to this map are added at the end, in their order in `other`.
+    pub fn merge(&mut self, other: &ParamsMap) {
+        for (key, values) in &other.0 {
+            if let Some(prev) = self.0.iter_mut().find(|(k, _)| k == key) {
+                prev.1.clone_from(values);
+            } else {
+                self.0.push((key.clone(), values.clone()));
             }
         }
-        None
+    }
+
+    /// Returns a new map with the keys of both maps, preferring the values in `other` for any key
+    /// that is in both. See [`merge`](Self::merge).
+    pub fn merged(&self, other: &ParamsMap) -> ParamsMap {
+        let mut merged = self.clone();
+        merged.merge(other);
+        merged
+    }
+
+    /// Sorts the params by key, keeping the order of values for each key.
+    pub(crate) fn sort(&mut self) {
+        self.0.sort_by(|(a, _), (b, _)| a.cmp(b));
     }
 
     /// Converts the map to a query string.
@@ -108,6 +200,45 @@ impl ParamsMap {
     }
 }
 
+/// Decides which values are kept when a route's path params and the URL's query params share a
+/// key. See [`use_merged_params`](crate::hooks::use_merged_params).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamsMergeStrategy {
+    /// Keep the path param, ignoring the query param with the same key.
+    #[default]
+    PathWins,
+    /// Replace the path param with all of the values of the query param with the same key.
+    QueryWins,
+}
+
+impl ParamsMergeStrategy {
+    /// Merges the query params into the path params, resolving any conflicts according to this
+    /// strategy. Keys that are only in `query` are added after the path params.
+    pub fn merge(self, path: &ParamsMap, query: &ParamsMap) -> ParamsMap {
+        let mut merged = path.clone();
+        for (key, values) in &query.0 {
+            match merged.0.iter_mut().find(|(k, _)| k == key) {
+                None => merged.0.push((key.clone(), values.clone())),
+                Some(prev) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        "path param {key:?} is also in the query string; \
+                         keeping the {} value",
+                        match self {
+                            Self::PathWins => "path",
+                            Self::QueryWins => "query",
+                        }
+                    );
+                    if self == Self::QueryWins {
+                        prev.1.clone_from(values);
+                    }
+                }
+            }
+        }
+        merged
+    }
+}
+
 impl<K, V> FromIterator<(K, V)> for ParamsMap
 where
     K: Into<Cow<'static, str>>,
@@ -115,11 +246,22 @@ where
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
 
+/// Inserts each pair with [`insert`](ParamsMap::insert), so values are added to any existing
+/// values for the same key.
+impl<K, V> Extend<(K, V)> for ParamsMap
+where
+    K: Into<Cow<'static, str>>,
+    V: Into<String>,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         for (key, value) in iter {
-            map.insert(key, value.into());
+            self.insert(key, value.into());
         }
-        map
     }
 }
 
@@ -187,13 +329,17 @@ where
 {
     fn into_param(
         value: Option<&str>,
-        _name: &str,
+        name: &str,
     ) -> Result<Self, ParamsError> {
         match value {
             None => Ok(None),
             Some(value) => match T::from_str(value) {
                 Ok(value) => Ok(Some(value)),
-                Err(e) => Err(ParamsError::Params(Arc::new(e))),
+                Err(e) => Err(ParamsError::InvalidParam {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    source: Arc::new(e),
+                }),
             },
         }
     }
@@ -219,7 +365,11 @@ mod option_param {
         ) -> Result<Self, ParamsError> {
             let value = value
                 .ok_or_else(|| ParamsError::MissingParam(name.to_string()))?;
-            Self::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
+            Self::from_str(value).map_err(|e| ParamsError::InvalidParam {
+                name: name.to_string(),
+                value: value.to_string(),
+                source: Arc::new(e),
+            })
         }
     }
 }
@@ -233,6 +383,16 @@ pub enum ParamsError {
     /// Something went wrong while deserializing a field.
     #[error("failed to deserialize parameters")]
     Params(Arc<dyn std::error::Error + Send + Sync>),
+    /// The value of a field could not be parsed into the field's type.
+    #[error("could not parse parameter {name} from {value:?}: {source}")]
+    InvalidParam {
+        /// The name of the field.
+        name: String,
+        /// The raw value of the parameter.
+        value: String,
+        /// The error returned while parsing the value.
+        source: Arc<dyn std::error::Error + Send + Sync>,
+    },
 }
 
 impl PartialEq for ParamsError {
@@ -240,6 +400,18 @@ impl PartialEq for ParamsError {
         match (self, other) {
             (Self::MissingParam(l0), Self::MissingParam(r0)) => l0 == r0,
             (Self::Params(_), Self::Params(_)) => false,
+            (
+                Self::InvalidParam {
+                    name: l_name,
+                    value: l_value,
+                    ..
+                },
+                Self::InvalidParam {
+                    name: r_name,
+                    value: r_value,
+                    ..
+                },
+            ) => l_name == r_name && l_value == r_value,
             _ => false,
         }
     }
@@ -260,4 +432,114 @@ mod tests {
         let query_string = map.to_query_string();
         assert_eq!(&query_string, "?param=a&param=b")
     }
+
+    #[test]
+    fn invalid_param_is_an_error() {
+        let err = Option::<i32>::into_param(Some("abc"), "page").unwrap_err();
+        assert!(matches!(
+            err,
+            ParamsError::InvalidParam { ref name, ref value, .. }
+                if name == "page" && value == "abc"
+        ));
+        assert_eq!(Option::<i32>::into_param(Some("12"), "page"), Ok(Some(12)));
+        assert_eq!(Option::<i32>::into_param(None, "page"), Ok(None));
+    }
+
+    #[test]
+    fn paramsmap_keeps_insertion_order() {
+        let mut map = [("z", "1"), ("a", "2"), ("m", "3"), ("b", "4")]
+            .into_iter()
+            .collect::<ParamsMap>();
+        assert_eq!(map.to_query_string(), "?z=1&a=2&m=3&b=4");
+        map.remove("a");
+        assert_eq!(map.to_query_string(), "?z=1&m=3&b=4");
+    }
+
+    #[test]
+    fn merge_strategy_resolves_conflicts() {
+        let path = ParamsMap::new().with("id", "1").with("tab", "posts");
+        let query = ParamsMap::new()
+            .with("id", "2")
+            .with("id", "3")
+            .with("q", "rust");
+        assert_eq!(
+            ParamsMergeStrategy::PathWins
+                .merge(&path, &query)
+                .iter_all()
+                .collect::<Vec<_>>(),
+            [("id", "1"), ("tab", "posts"), ("q", "rust")]
+        );
+        assert_eq!(
+            ParamsMergeStrategy::QueryWins
+                .merge(&path, &query)
+                .iter_all()
+                .collect::<Vec<_>>(),
+            [("id", "2"), ("id", "3"), ("tab", "posts"), ("q", "rust")]
+        );
+    }
+
+    #[test]
+    fn paramsmap_from_iter_and_extend() {
+        let mut map = [("id".to_string(), "1".to_string())]
+            .into_iter()
+            .collect::<ParamsMap>();
+        map.extend([
+            ("tag".to_string(), "rust".to_string()),
+            ("tag".to_string(), "wasm".to_string()),
+        ]);
+        assert_eq!(
+            map.iter_all().collect::<Vec<_>>(),
+            [("id", "1"), ("tag", "rust"), ("tag", "wasm")]
+        );
+    }
+
+    #[test]
+    fn paramsmap_merge_prefers_other() {
+        let defaults = ParamsMap::new()
+            .with("sort", "name")
+            .with("tag", "a")
+            .with("tag", "b");
+        let params = ParamsMap::new().with("tag", "c").with("page", "2");
+        let merged = defaults.merged(&params);
+        assert_eq!(
+            merged.iter_all().collect::<Vec<_>>(),
+            [("sort", "name"), ("tag", "c"), ("page", "2")]
+        );
+        assert_eq!(defaults.get_all("tag"), Some(vec!["a".into(), "b".into()]));
+    }
+
+    #[test]
+    fn paramsmap_get_parsed() {
+        let map = [("page", "2"), ("sort", "name")]
+            .into_iter()
+            .collect::<ParamsMap>();
+        assert_eq!(map.get_parsed::<u32>("page"), Ok(2));
+        assert_eq!(
+            map.get_parsed::<u32>("limit"),
+            Err(ParamsError::MissingParam("limit".to_string()))
+        );
+        assert!(matches!(
+            map.get_parsed::<u32>("sort"),
+            Err(ParamsError::InvalidParam { ref name, ref value, .. })
+                if name == "sort" && value == "name"
+        ));
+        assert_eq!(map.get_parsed_or("page", 1), 2);
+        assert_eq!(map.get_parsed_or("sort", 1), 1);
+        assert_eq!(map.get_parsed_or("limit", 20), 20);
+    }
+
+    #[test]
+    fn paramsmap_repeated_keys() {
+        let map = [("tag", "rust"), ("page", "1"), ("tag", "wasm")]
+            .into_iter()
+            .collect::<ParamsMap>();
+        assert_eq!(
+            map.get_all("tag"),
+            Some(vec!["rust".to_string(), "wasm".to_string()])
+        );
+        assert_eq!(
+            map.iter_all().collect::<Vec<_>>(),
+            [("tag", "rust"), ("tag", "wasm"), ("page", "1")]
+        );
+    }
 }
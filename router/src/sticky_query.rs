@@ -0,0 +1,151 @@
+use crate::location::Url;
+use or_poisoned::OrPoisoned;
+use std::{
+    fmt::{self, Debug},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// What happens to a sticky query parameter when the URL being navigated to already has a value
+/// for it. See [`use_sticky_query`](crate::hooks::use_sticky_query).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StickyMergeStrategy {
+    /// Keep the value in the URL being navigated to.
+    #[default]
+    Preserve,
+    /// Replace the value in the URL being navigated to with the current one.
+    Replace,
+}
+
+/// Configures query parameters registered with
+/// [`use_sticky_query_with_config`](crate::hooks::use_sticky_query_with_config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StickyQueryConfig {
+    /// What happens when the URL being navigated to already has a value for a sticky parameter.
+    pub merge_strategy: StickyMergeStrategy,
+}
+
+/// The query parameters that are carried over from the current URL into each navigation.
+#[derive(Clone, Default)]
+pub(crate) struct StickyQuery {
+    next_id: Arc<AtomicUsize>,
+    keys: Arc<Mutex<Vec<(usize, String, StickyQueryConfig)>>>,
+}
+
+impl Debug for StickyQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StickyQuery")
+            .field(
+                "keys",
+                &self
+                    .keys
+                    .lock()
+                    .or_poisoned()
+                    .iter()
+                    .map(|(_, key, _)| key.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl StickyQuery {
+    /// Makes each of the given keys sticky, until [`unregister`](Self::unregister) is called with
+    /// the returned id.
+    pub fn register(
+        &self,
+        keys: impl IntoIterator<Item = String>,
+        config: StickyQueryConfig,
+    ) -> usize {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.keys
+            .lock()
+            .or_poisoned()
+            .extend(keys.into_iter().map(|key| (id, key, config)));
+        id
+    }
+
+    /// Removes the keys added by a call to [`register`](Self::register).
+    pub fn unregister(&self, id: usize) {
+        self.keys
+            .lock()
+            .or_poisoned()
+            .retain(|(key_id, _, _)| *key_id != id);
+    }
+
+    /// Copies the values of the sticky parameters in `from` into `to`. Returns `true` if `to`
+    /// was changed.
+    pub fn apply(&self, from: &Url, to: &mut Url) -> bool {
+        if from.origin() != to.origin() {
+            return false;
+        }
+
+        let keys = self.keys.lock().or_poisoned().clone();
+        let mut changed = false;
+        for (_, key, config) in keys {
+            let Some(value) = from.search_params().get(&key) else {
+                continue;
+            };
+            let preserve = config.merge_strategy
+                == StickyMergeStrategy::Preserve
+                && to.search_params().get_str(&key).is_some();
+            if !preserve
+                && to.search_params().get_str(&key) != Some(value.as_str())
+            {
+                to.search_params_mut().replace(key, value);
+                changed = true;
+            }
+        }
+        if changed {
+            *to.search_mut() = to
+                .search_params()
+                .to_query_string()
+                .trim_start_matches('?')
+                .into();
+        }
+        changed
+    }
+}
+
+// building a query string uses `Url::escape`, which calls into JS outside `ssr`
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{StickyMergeStrategy, StickyQuery, StickyQueryConfig};
+    use crate::location::{LocationProvider, MemoryRouter};
+
+    #[test]
+    fn carries_sticky_params_over() {
+        let sticky = StickyQuery::default();
+        sticky.register(["lang".to_string()], StickyQueryConfig::default());
+        let from = MemoryRouter::parse("/a?lang=fr&page=2").unwrap();
+
+        let mut to = MemoryRouter::parse("/b").unwrap();
+        assert!(sticky.apply(&from, &mut to));
+        assert_eq!(to.search(), "lang=fr");
+
+        let mut to = MemoryRouter::parse("/b?lang=de").unwrap();
+        assert!(!sticky.apply(&from, &mut to));
+        assert_eq!(to.search(), "lang=de");
+    }
+
+    #[test]
+    fn replace_strategy_overrides_destination() {
+        let sticky = StickyQuery::default();
+        let id = sticky.register(
+            ["lang".to_string()],
+            StickyQueryConfig {
+                merge_strategy: StickyMergeStrategy::Replace,
+            },
+        );
+        let from = MemoryRouter::parse("/a?lang=fr").unwrap();
+        let mut to = MemoryRouter::parse("/b?lang=de").unwrap();
+        assert!(sticky.apply(&from, &mut to));
+        assert_eq!(to.search(), "lang=fr");
+
+        sticky.unregister(id);
+        let mut to = MemoryRouter::parse("/b").unwrap();
+        assert!(!sticky.apply(&from, &mut to));
+    }
+}